@@ -0,0 +1,735 @@
+use crate::creator::error::SvgError;
+use crate::creator::format::decimal;
+use crate::creator::node::Node;
+use crate::creator::path::parse;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+///
+/// # The kind of an animation
+///
+pub trait Animated {
+    const TAG: &'static str;
+}
+
+///
+/// # An animation of an attribute value
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attribute;
+
+///
+/// # An animation of the transform attribute
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transformation;
+
+///
+/// # A move along a path
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion;
+
+///
+/// # A value set for a duration
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setter;
+
+impl Animated for Attribute {
+    const TAG: &'static str = "animate";
+}
+
+impl Animated for Transformation {
+    const TAG: &'static str = "animateTransform";
+}
+
+impl Animated for Motion {
+    const TAG: &'static str = "animateMotion";
+}
+
+impl Animated for Setter {
+    const TAG: &'static str = "set";
+}
+
+pub type Animate = Animation<Attribute>;
+pub type AnimateTransform = Animation<Transformation>;
+pub type AnimateMotion = Animation<Motion>;
+pub type Set = Animation<Setter>;
+
+///
+/// # The start of an animation
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Begin {
+    ///
+    /// # A delay after the load of the document
+    ///
+    Offset(Duration),
+    ///
+    /// # An event, as `click`, on the animated element or another one
+    ///
+    Event {
+        target: Option<String>,
+        event: String,
+        offset: Duration,
+    },
+    ///
+    /// # The start or the end of another animation
+    ///
+    Sync {
+        target: String,
+        end: bool,
+        offset: Duration,
+    },
+    ///
+    /// # Never, until started by a script
+    ///
+    Indefinite,
+}
+
+impl Begin {
+    ///
+    /// # A delay after the load of the document
+    ///
+    #[must_use]
+    pub const fn at(offset: Duration) -> Self {
+        Self::Offset(offset)
+    }
+
+    ///
+    /// # An event on the animated element
+    ///
+    /// - `event` The event, as `click` or `mouseover`
+    ///
+    #[must_use]
+    pub fn event(event: &str) -> Self {
+        Self::Event {
+            target: None,
+            event: event.to_string(),
+            offset: Duration::ZERO,
+        }
+    }
+
+    ///
+    /// # An event on another element
+    ///
+    /// - `target`  The id of the element
+    /// - `event`   The event, as `click` or `mouseover`
+    ///
+    #[must_use]
+    pub fn event_on(target: &str, event: &str) -> Self {
+        Self::Event {
+            target: Some(target.trim_start_matches('#').to_string()),
+            event: event.to_string(),
+            offset: Duration::ZERO,
+        }
+    }
+
+    ///
+    /// # The end of another animation
+    ///
+    /// - `target` The id of the animation
+    ///
+    #[must_use]
+    pub fn after(target: &str) -> Self {
+        Self::Sync {
+            target: target.trim_start_matches('#').to_string(),
+            end: true,
+            offset: Duration::ZERO,
+        }
+    }
+
+    ///
+    /// # The start of another animation
+    ///
+    /// - `target` The id of the animation
+    ///
+    #[must_use]
+    pub fn with(target: &str) -> Self {
+        Self::Sync {
+            target: target.trim_start_matches('#').to_string(),
+            end: false,
+            offset: Duration::ZERO,
+        }
+    }
+
+    ///
+    /// # Delay the start
+    ///
+    /// - `delay` The time added to the start
+    ///
+    #[must_use]
+    pub fn delay(mut self, delay: Duration) -> Self {
+        match &mut self {
+            Self::Offset(offset) | Self::Event { offset, .. } | Self::Sync { offset, .. } => {
+                *offset += delay
+            }
+            Self::Indefinite => {}
+        }
+        self
+    }
+}
+
+impl Display for Begin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let delay = |offset: &Duration| {
+            if offset.is_zero() {
+                String::new()
+            } else {
+                format!("+{}", clock(*offset))
+            }
+        };
+        match self {
+            Self::Offset(offset) => f.write_str(clock(*offset).as_str()),
+            Self::Event {
+                target,
+                event,
+                offset,
+            } => match target {
+                Some(target) => write!(f, "{target}.{event}{}", delay(offset)),
+                None => write!(f, "{event}{}", delay(offset)),
+            },
+            Self::Sync {
+                target,
+                end,
+                offset,
+            } => {
+                let sync = if *end { "end" } else { "begin" };
+                write!(f, "{target}.{sync}{}", delay(offset))
+            }
+            Self::Indefinite => f.write_str("indefinite"),
+        }
+    }
+}
+
+impl From<Duration> for Begin {
+    fn from(offset: Duration) -> Self {
+        Self::Offset(offset)
+    }
+}
+
+///
+/// # The number of iterations of an animation
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Repeat {
+    ///
+    /// # A number of iterations, can be fractional
+    ///
+    Count(f64),
+    ///
+    /// # A total duration of the iterations
+    ///
+    For(Duration),
+    ///
+    /// # Forever
+    ///
+    Indefinite,
+}
+
+///
+/// # The interpolation between the values
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CalcMode {
+    ///
+    /// # Jump from a value to the next
+    ///
+    Discrete,
+    ///
+    /// # A constant speed on each interval
+    ///
+    #[default]
+    Linear,
+    ///
+    /// # A constant speed on the whole animation
+    ///
+    Paced,
+    ///
+    /// # A bezier timing function on each interval
+    ///
+    Spline,
+}
+
+impl CalcMode {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Discrete => "discrete",
+            Self::Linear => "linear",
+            Self::Paced => "paced",
+            Self::Spline => "spline",
+        }
+    }
+}
+
+///
+/// # The timing function applied to each interval
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    ///
+    /// # The control points of a cubic bezier between (0, 0) and (1, 1)
+    ///
+    CubicBezier(f64, f64, f64, f64),
+}
+
+impl Easing {
+    ///
+    /// # The control points of the timing function
+    ///
+    #[must_use]
+    pub const fn control_points(self) -> (f64, f64, f64, f64) {
+        match self {
+            Self::Linear => (0.0, 0.0, 1.0, 1.0),
+            Self::Ease => (0.25, 0.1, 0.25, 1.0),
+            Self::EaseIn => (0.42, 0.0, 1.0, 1.0),
+            Self::EaseOut => (0.0, 0.0, 0.58, 1.0),
+            Self::EaseInOut => (0.42, 0.0, 0.58, 1.0),
+            Self::CubicBezier(x1, y1, x2, y2) => (x1, y1, x2, y2),
+        }
+    }
+}
+
+///
+/// # The type of `animateTransform`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransformType {
+    Translate,
+    Scale,
+    Rotate,
+    SkewX,
+    SkewY,
+}
+
+impl TransformType {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Translate => "translate",
+            Self::Scale => "scale",
+            Self::Rotate => "rotate",
+            Self::SkewX => "skewX",
+            Self::SkewY => "skewY",
+        }
+    }
+}
+
+///
+/// # The rotation of an element moving along a path
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MotionRotate {
+    ///
+    /// # Follow the direction of the path
+    ///
+    Auto,
+    ///
+    /// # Follow the opposite direction of the path
+    ///
+    AutoReverse,
+    ///
+    /// # A fixed angle in degrees
+    ///
+    Angle(f64),
+}
+
+impl Display for MotionRotate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::AutoReverse => f.write_str("auto-reverse"),
+            Self::Angle(angle) => f.write_str(decimal(*angle, 6).as_str()),
+        }
+    }
+}
+
+///
+/// # An animation element
+///
+/// The animation is added to the current element with `Svg::animation` or
+/// to a typed element with `Element::animation`.
+///
+#[derive(Debug)]
+pub struct Animation<K: Animated> {
+    attributes: Vec<(String, String)>,
+    begins: Vec<String>,
+    values: Option<Vec<String>>,
+    key_times: Option<Vec<f64>>,
+    easing: Option<Easing>,
+    children: Vec<Node>,
+    error: Option<SvgError>,
+    kind: PhantomData<K>,
+}
+
+impl<K: Animated> Animation<K> {
+    fn empty() -> Self {
+        Self {
+            attributes: Vec::new(),
+            begins: Vec::new(),
+            values: None,
+            key_times: None,
+            easing: None,
+            children: Vec::new(),
+            error: None,
+            kind: PhantomData,
+        }
+    }
+
+    ///
+    /// # Define an id, referenced by the begin of other animations
+    ///
+    /// - `id` The id
+    ///
+    #[must_use]
+    pub fn id(self, id: &str) -> Self {
+        self.attr("id", id.to_string())
+    }
+
+    ///
+    /// # Define the duration of an iteration
+    ///
+    /// - `duration` The duration
+    ///
+    #[must_use]
+    pub fn dur(self, duration: Duration) -> Self {
+        self.attr("dur", clock(duration))
+    }
+
+    ///
+    /// # Add a start condition, the first one met starts the animation
+    ///
+    /// - `begin` The condition, or a delay after the load
+    ///
+    #[must_use]
+    pub fn begin<B: Into<Begin>>(mut self, begin: B) -> Self {
+        self.begins.push(begin.into().to_string());
+        let begins = self.begins.join(";");
+        self.attr("begin", begins)
+    }
+
+    ///
+    /// # Define the number of iterations
+    ///
+    /// - `repeat` The iterations, one when not defined
+    ///
+    #[must_use]
+    pub fn repeat(self, repeat: Repeat) -> Self {
+        match repeat {
+            Repeat::Count(count) => self.attr("repeatCount", decimal(count, 6)),
+            Repeat::For(duration) => self.attr("repeatDur", clock(duration)),
+            Repeat::Indefinite => self.attr("repeatCount", "indefinite".to_string()),
+        }
+    }
+
+    ///
+    /// # Keep the last value when the animation ends
+    ///
+    #[must_use]
+    pub fn freeze(self) -> Self {
+        self.attr("fill", "freeze".to_string())
+    }
+
+    ///
+    /// # The element written in the document
+    ///
+    #[must_use]
+    pub fn to_node(&self) -> Node {
+        let mut node = Node::new(K::TAG);
+        for (name, value) in &self.attributes {
+            node.set_attribute(name, value);
+        }
+        if let Some(values) = &self.values {
+            node.set_attribute("values", values.join(";").as_str());
+        }
+        let intervals = self
+            .values
+            .as_ref()
+            .map_or(1, |v| v.len().saturating_sub(1));
+        let easing = self
+            .easing
+            .filter(|easing| intervals > 0 || *easing == Easing::Linear);
+        let key_times = match (&self.key_times, easing) {
+            (Some(times), _) => Some(times.clone()),
+            (None, Some(easing)) if easing != Easing::Linear => {
+                let count = intervals as f64;
+                Some((0..=intervals).map(|i| i as f64 / count).collect())
+            }
+            _ => None,
+        };
+        if let Some(times) = key_times {
+            node.set_attribute("keyTimes", list(&times, ";").as_str());
+        }
+        match easing {
+            Some(Easing::Linear) => {
+                node.set_attribute("calcMode", CalcMode::Linear.as_str());
+            }
+            Some(easing) => {
+                let (x1, y1, x2, y2) = easing.control_points();
+                let spline = list(&[x1, y1, x2, y2], " ");
+                node.set_attribute("calcMode", CalcMode::Spline.as_str())
+                    .set_attribute("keySplines", vec![spline; intervals].join(";").as_str());
+            }
+            None => {}
+        }
+        for child in &self.children {
+            node.append(child.clone());
+        }
+        node
+    }
+
+    ///
+    /// # The first error raised while building the animation
+    ///
+    pub(crate) fn take_error(&mut self) -> Option<SvgError> {
+        if let (Some(values), Some(times)) = (&self.values, &self.key_times) {
+            if values.len() != times.len() {
+                let message = format!("{} key times for {} values", times.len(), values.len());
+                self.error = self
+                    .error
+                    .take()
+                    .or(Some(SvgError::InvalidAnimation(message)));
+            }
+        }
+        self.error.take()
+    }
+
+    fn attr(mut self, name: &str, value: String) -> Self {
+        match self.attributes.iter_mut().find(|(n, _)| n == name) {
+            Some(attribute) => attribute.1 = value,
+            None => self.attributes.push((name.to_string(), value)),
+        }
+        self
+    }
+
+    fn interpolated<V: Display>(mut self, values: &[V]) -> Self {
+        self.values = Some(values.iter().map(ToString::to_string).collect());
+        self
+    }
+}
+
+macro_rules! interpolation {
+    ($($kind:ty),*) => {
+        $(
+            impl Animation<$kind> {
+                ///
+                /// # Define the values taken in order
+                ///
+                /// - `values` The values
+                ///
+                #[must_use]
+                pub fn values<V: Display>(self, values: &[V]) -> Self {
+                    self.interpolated(values)
+                }
+
+                ///
+                /// # Define the start and the end values
+                ///
+                /// - `from`    The start value
+                /// - `to`      The end value
+                ///
+                #[must_use]
+                pub fn between<F: Display, T: Display>(self, from: F, to: T) -> Self {
+                    self.attr("from", from.to_string())
+                        .attr("to", to.to_string())
+                }
+
+                ///
+                /// # Define the progress of each value, between 0 and 1
+                ///
+                /// - `times` One time per value
+                ///
+                #[must_use]
+                pub fn key_times(mut self, times: &[f64]) -> Self {
+                    self.key_times = Some(times.to_vec());
+                    self
+                }
+
+                ///
+                /// # Define the interpolation between the values
+                ///
+                /// - `mode` The interpolation, replaced by `easing`
+                ///
+                #[must_use]
+                pub fn calc_mode(mut self, mode: CalcMode) -> Self {
+                    self.easing = None;
+                    self.attr("calcMode", mode.as_str().to_string())
+                }
+
+                ///
+                /// # Apply a timing function on each interval
+                ///
+                /// The key times are spread evenly when not defined. A curve is
+                /// ignored when the values hold less than two entries.
+                ///
+                /// - `easing` The timing function
+                ///
+                #[must_use]
+                pub fn easing(mut self, easing: Easing) -> Self {
+                    self.easing = Some(easing);
+                    self
+                }
+
+                ///
+                /// # Add the animated value to the value of the element
+                ///
+                #[must_use]
+                pub fn additive(self) -> Self {
+                    self.attr("additive", "sum".to_string())
+                }
+
+                ///
+                /// # Add the value of the previous iteration to each iteration
+                ///
+                #[must_use]
+                pub fn accumulate(self) -> Self {
+                    self.attr("accumulate", "sum".to_string())
+                }
+            }
+        )*
+    };
+}
+
+interpolation!(Attribute, Transformation, Motion);
+
+impl Animation<Attribute> {
+    ///
+    /// # Constructor
+    ///
+    /// - `attribute` The name of the animated attribute
+    ///
+    #[must_use]
+    pub fn new(attribute: &str) -> Self {
+        Self::empty().attr("attributeName", attribute.to_string())
+    }
+}
+
+impl Animation<Transformation> {
+    ///
+    /// # Constructor
+    ///
+    /// - `kind` The animated transformation
+    ///
+    #[must_use]
+    pub fn new(kind: TransformType) -> Self {
+        Self::empty()
+            .attr("attributeName", "transform".to_string())
+            .attr("type", kind.as_str().to_string())
+    }
+}
+
+impl Animation<Motion> {
+    ///
+    /// # Constructor
+    ///
+    #[must_use]
+    pub fn new() -> Self {
+        Self::empty()
+    }
+
+    ///
+    /// # Move along a path data
+    ///
+    /// - `d` The path data, a string or a `PathBuilder`, reported on save when invalid
+    ///
+    #[must_use]
+    pub fn path<D: Display>(mut self, d: D) -> Self {
+        let d = d.to_string();
+        if let Err(e) = parse(d.as_str()) {
+            self.error = self.error.or(Some(e));
+        }
+        self.attr("path", d)
+    }
+
+    ///
+    /// # Move along a path of the document
+    ///
+    /// - `id` The id of the path
+    ///
+    #[must_use]
+    pub fn mpath(mut self, id: &str) -> Self {
+        let mut mpath = Node::new("mpath");
+        mpath.set_attribute(
+            "xlink:href",
+            format!("#{}", id.trim_start_matches('#')).as_str(),
+        );
+        self.children = vec![mpath];
+        self
+    }
+
+    ///
+    /// # Define the rotation of the element along the path
+    ///
+    /// - `rotate` The rotation
+    ///
+    #[must_use]
+    pub fn rotate(self, rotate: MotionRotate) -> Self {
+        self.attr("rotate", rotate.to_string())
+    }
+
+    ///
+    /// # Define the progress along the path for each key time, between 0 and 1
+    ///
+    /// - `points` One point per key time
+    ///
+    #[must_use]
+    pub fn key_points(self, points: &[f64]) -> Self {
+        self.attr("keyPoints", list(points, ";"))
+    }
+}
+
+impl Default for Animation<Motion> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Animation<Setter> {
+    ///
+    /// # Constructor
+    ///
+    /// - `attribute`   The name of the attribute
+    /// - `value`       The value set
+    ///
+    #[must_use]
+    pub fn new<V: Display>(attribute: &str, value: V) -> Self {
+        Self::empty()
+            .attr("attributeName", attribute.to_string())
+            .attr("to", value.to_string())
+    }
+}
+
+///
+/// # Format a clock value, in seconds or in milliseconds
+///
+fn clock(duration: Duration) -> String {
+    if duration.subsec_nanos() == 0 {
+        format!("{}s", duration.as_secs())
+    } else if duration.subsec_nanos().is_multiple_of(1_000_000) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{}ms", duration.as_secs_f64() * 1000.0)
+    }
+}
+
+fn list(values: &[f64], separator: &str) -> String {
+    values
+        .iter()
+        .map(|v| decimal(*v, 6))
+        .collect::<Vec<String>>()
+        .join(separator)
+}
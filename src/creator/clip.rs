@@ -0,0 +1,68 @@
+use crate::creator::definition::{Drawing, Template, Units};
+use crate::creator::transform::Transform;
+
+///
+/// # The kind of the clip path definitions
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clipping;
+
+impl Drawing for Clipping {
+    const TAG: &'static str = "clipPath";
+}
+
+///
+/// # A clip path definition
+///
+/// Only the parts of the elements referencing the clip path with `clip_path`
+/// that lie inside the shapes drawn with `content` are drawn.
+///
+pub type ClipPath = Template<Clipping>;
+
+impl ClipPath {
+    ///
+    /// # Constructor
+    ///
+    /// - `id` The id of the clip path
+    ///
+    #[must_use]
+    pub fn new(id: &str) -> Self {
+        Self::with_id(id)
+    }
+
+    ///
+    /// # Define the coordinate system of the content
+    ///
+    /// - `units` The units, `UserSpaceOnUse` when not defined
+    ///
+    #[must_use]
+    pub fn units(self, units: Units) -> Self {
+        self.attr("clipPathUnits", units.to_string())
+    }
+
+    ///
+    /// # Transform the clip path
+    ///
+    /// - `transform` The transformation
+    ///
+    #[must_use]
+    pub fn transform(self, transform: &Transform) -> Self {
+        self.attr("transform", transform.to_string())
+    }
+
+    ///
+    /// # The circle inscribed in the bounding box of the clipped element
+    ///
+    /// Crops a square image into a disc, as the avatars.
+    ///
+    /// - `id` The id of the clip path
+    ///
+    #[must_use]
+    pub fn circle(id: &str) -> Self {
+        Self::new(id)
+            .units(Units::ObjectBoundingBox)
+            .content(|clip| {
+                clip.circle().cx(0.5).cy(0.5).r(0.5).close_circle();
+            })
+    }
+}
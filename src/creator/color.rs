@@ -0,0 +1,557 @@
+use crate::creator::error::SvgError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+///
+/// # A color with an alpha channel
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    ///
+    /// # The opacity between 0 and 1
+    ///
+    pub a: f64,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+    pub const TRANSPARENT: Self = Self::rgba(0, 0, 0, 0.0);
+
+    ///
+    /// # An opaque color
+    ///
+    /// - `r` The red channel
+    /// - `g` The green channel
+    /// - `b` The blue channel
+    ///
+    #[must_use]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+
+    ///
+    /// # A color with an opacity
+    ///
+    /// - `r` The red channel
+    /// - `g` The green channel
+    /// - `b` The blue channel
+    /// - `a` The opacity between 0 and 1
+    ///
+    #[must_use]
+    pub const fn rgba(r: u8, g: u8, b: u8, a: f64) -> Self {
+        Self { r, g, b, a }
+    }
+
+    ///
+    /// # An opaque color from a `0xrrggbb` value
+    ///
+    /// - `hex` The color value
+    ///
+    #[must_use]
+    pub const fn hex(hex: u32) -> Self {
+        Self::rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+    }
+
+    ///
+    /// # An opaque color from hsl
+    ///
+    /// - `h` The hue in degrees
+    /// - `s` The saturation between 0 and 1
+    /// - `l` The lightness between 0 and 1
+    ///
+    #[must_use]
+    pub fn hsl(h: f64, s: f64, l: f64) -> Self {
+        Self::hsla(h, s, l, 1.0)
+    }
+
+    ///
+    /// # A color from hsl with an opacity
+    ///
+    /// - `h` The hue in degrees
+    /// - `s` The saturation between 0 and 1
+    /// - `l` The lightness between 0 and 1
+    /// - `a` The opacity between 0 and 1
+    ///
+    #[must_use]
+    pub fn hsla(h: f64, s: f64, l: f64, a: f64) -> Self {
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h {
+            h if h < 1.0 => (c, x, 0.0),
+            h if h < 2.0 => (x, c, 0.0),
+            h if h < 3.0 => (0.0, c, x),
+            h if h < 4.0 => (0.0, x, c),
+            h if h < 5.0 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        Self::rgba(
+            channel(r + m),
+            channel(g + m),
+            channel(b + m),
+            a.clamp(0.0, 1.0),
+        )
+    }
+
+    ///
+    /// # Convert to hsl
+    ///
+    /// # Return
+    ///
+    /// The hue in degrees, the saturation and the lightness between 0 and 1
+    ///
+    #[must_use]
+    pub fn to_hsl(self) -> (f64, f64, f64) {
+        let r = f64::from(self.r) / 255.0;
+        let g = f64::from(self.g) / 255.0;
+        let b = f64::from(self.b) / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return (0.0, 0.0, l);
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if (max - r).abs() < f64::EPSILON {
+            ((g - b) / d).rem_euclid(6.0)
+        } else if (max - g).abs() < f64::EPSILON {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    ///
+    /// # Increase the lightness
+    ///
+    /// - `amount` The lightness to add between 0 and 1
+    ///
+    #[must_use]
+    pub fn lighten(self, amount: f64) -> Self {
+        let (h, s, l) = self.to_hsl();
+        Self::hsla(h, s, l + amount, self.a)
+    }
+
+    ///
+    /// # Decrease the lightness
+    ///
+    /// - `amount` The lightness to remove between 0 and 1
+    ///
+    #[must_use]
+    pub fn darken(self, amount: f64) -> Self {
+        self.lighten(-amount)
+    }
+
+    ///
+    /// # Mix with another color
+    ///
+    /// - `other`   The color to mix
+    /// - `weight`  The part of `other` between 0 and 1
+    ///
+    #[must_use]
+    pub fn mix(self, other: Self, weight: f64) -> Self {
+        let w = weight.clamp(0.0, 1.0);
+        let blend = |a: u8, b: u8| channel((f64::from(a) * (1.0 - w) + f64::from(b) * w) / 255.0);
+        Self::rgba(
+            blend(self.r, other.r),
+            blend(self.g, other.g),
+            blend(self.b, other.b),
+            self.a * (1.0 - w) + other.a * w,
+        )
+    }
+
+    ///
+    /// # Change the opacity
+    ///
+    /// - `a` The opacity between 0 and 1
+    ///
+    #[must_use]
+    pub fn alpha(self, a: f64) -> Self {
+        Self::rgba(self.r, self.g, self.b, a.clamp(0.0, 1.0))
+    }
+
+    ///
+    /// # The `#rrggbb` notation, `#rrggbbaa` for a translucent color
+    ///
+    #[must_use]
+    pub fn to_hex(self) -> String {
+        if self.a >= 1.0 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!(
+                "#{:02x}{:02x}{:02x}{:02x}",
+                self.r,
+                self.g,
+                self.b,
+                channel(self.a)
+            )
+        }
+    }
+
+    ///
+    /// # Find a css named color
+    ///
+    /// - `name` The color name, case insensitive
+    ///
+    #[must_use]
+    pub fn named(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name == "transparent" {
+            return Some(Self::TRANSPARENT);
+        }
+        NAMED
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, hex)| Self::hex(*hex))
+    }
+
+    fn parse_hex(hex: &str) -> Option<Self> {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let digit = |i: usize| u8::from_str_radix(&hex[i..=i], 16).ok().map(|d| d * 17);
+        let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        let alpha = |a: u8| f64::from(a) / 255.0;
+        match hex.len() {
+            3 => Some(Self::rgb(digit(0)?, digit(1)?, digit(2)?)),
+            4 => Some(Self::rgba(
+                digit(0)?,
+                digit(1)?,
+                digit(2)?,
+                alpha(digit(3)?),
+            )),
+            6 => Some(Self::rgb(pair(0)?, pair(2)?, pair(4)?)),
+            8 => Some(Self::rgba(pair(0)?, pair(2)?, pair(4)?, alpha(pair(6)?))),
+            _ => None,
+        }
+    }
+
+    fn parse_function(s: &str) -> Option<Self> {
+        let (name, rest) = s.split_once('(')?;
+        let args = rest.strip_suffix(')')?;
+        let args: Vec<&str> = args
+            .split([',', ' ', '/'])
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .collect();
+        let alpha = match args.get(3) {
+            Some(a) => ratio(a, 1.0)?,
+            None => 1.0,
+        };
+        if args.len() < 3 || args.len() > 4 {
+            return None;
+        }
+        match name.trim().to_ascii_lowercase().as_str() {
+            "rgb" | "rgba" => {
+                let c = |a: &str| ratio(a, 255.0).map(channel);
+                Some(Self::rgba(c(args[0])?, c(args[1])?, c(args[2])?, alpha))
+            }
+            "hsl" | "hsla" => {
+                let h = args[0].trim_end_matches("deg").parse().ok()?;
+                let s = args[1].strip_suffix('%')?.parse::<f64>().ok()? / 100.0;
+                let l = args[2].strip_suffix('%')?.parse::<f64>().ok()? / 100.0;
+                Some(Self::hsla(h, s, l, alpha))
+            }
+            _ => None,
+        }
+    }
+}
+
+///
+/// # Convert a ratio between 0 and 1 to a channel
+///
+fn channel(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+///
+/// # Parse a number or a percentage to a ratio between 0 and 1
+///
+/// - `arg`     The value
+/// - `scale`   The value of a full number
+///
+fn ratio(arg: &str, scale: f64) -> Option<f64> {
+    let value = match arg.strip_suffix('%') {
+        Some(p) => p.parse::<f64>().ok()? / 100.0,
+        None => arg.parse::<f64>().ok()? / scale,
+    };
+    Some(value.clamp(0.0, 1.0))
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.to_hex().as_str())
+    }
+}
+
+impl FromStr for Color {
+    type Err = SvgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let color = s.trim();
+        let parsed = match color.strip_prefix('#') {
+            Some(hex) => Self::parse_hex(hex),
+            None if color.contains('(') => Self::parse_function(color),
+            None => Self::named(color),
+        };
+        parsed.ok_or_else(|| SvgError::InvalidColor(s.to_string()))
+    }
+}
+
+///
+/// # The value of the fill and stroke attributes
+///
+/// A color is written opaque, its alpha is written by the setters in the
+/// `fill-opacity` or `stroke-opacity` attribute.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    None,
+    CurrentColor,
+    ///
+    /// # The paint of the parent element
+    ///
+    Inherit,
+    Color(Color),
+    ///
+    /// # A reference to a paint server by his id
+    ///
+    Url(String),
+}
+
+impl Paint {
+    ///
+    /// # The opacity of a translucent color
+    ///
+    /// None for an opaque color and the other paints
+    ///
+    #[must_use]
+    pub fn opacity(&self) -> Option<f64> {
+        match self {
+            Self::Color(c) if c.a < 1.0 => Some(c.a.clamp(0.0, 1.0)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Paint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("none"),
+            Self::CurrentColor => f.write_str("currentColor"),
+            Self::Inherit => f.write_str("inherit"),
+            Self::Color(c) => c.alpha(1.0).fmt(f),
+            Self::Url(id) => write!(f, "url(#{id})"),
+        }
+    }
+}
+
+impl FromStr for Paint {
+    type Err = SvgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let paint = s.trim();
+        if paint.eq_ignore_ascii_case("none") {
+            return Ok(Self::None);
+        }
+        if paint.eq_ignore_ascii_case("currentColor") {
+            return Ok(Self::CurrentColor);
+        }
+        if paint.eq_ignore_ascii_case("inherit") {
+            return Ok(Self::Inherit);
+        }
+        if let Some(url) = paint.strip_prefix("url(").and_then(|u| u.strip_suffix(')')) {
+            let id = url.trim().trim_matches(['"', '\'']).trim_start_matches('#');
+            if id.is_empty() {
+                return Err(SvgError::InvalidColor(s.to_string()));
+            }
+            return Ok(Self::Url(id.to_string()));
+        }
+        Ok(Self::Color(paint.parse()?))
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Self::Color(color)
+    }
+}
+
+impl TryFrom<&str> for Paint {
+    type Error = SvgError;
+
+    fn try_from(paint: &str) -> Result<Self, Self::Error> {
+        paint.parse()
+    }
+}
+
+impl TryFrom<&str> for Color {
+    type Error = SvgError;
+
+    fn try_from(color: &str) -> Result<Self, Self::Error> {
+        color.parse()
+    }
+}
+
+///
+/// # The css named colors
+///
+const NAMED: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
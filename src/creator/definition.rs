@@ -0,0 +1,172 @@
+use crate::creator::error::SvgError;
+use crate::creator::length::Length;
+use crate::creator::node::{Child, Node};
+use crate::creator::svg::Svg;
+use crate::creator::viewport::ViewBox;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+
+///
+/// # A resource written in the `<defs>` of the document
+///
+/// A definition is registered with `Svg::define` and referenced by its id.
+///
+pub trait Definition {
+    ///
+    /// # The id of the resource
+    ///
+    fn id(&self) -> &str;
+
+    ///
+    /// # The element written in the defs
+    ///
+    fn to_node(&self) -> Node;
+
+    ///
+    /// # The id of another definition this one inherits from
+    ///
+    fn inherits(&self) -> Option<&str> {
+        None
+    }
+
+    ///
+    /// # The first error raised while building the content
+    ///
+    fn take_error(&mut self) -> Option<SvgError> {
+        None
+    }
+}
+
+///
+/// # The coordinate system of the attributes of a resource
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    ///
+    /// # The user space of the element referencing the resource
+    ///
+    UserSpaceOnUse,
+    ///
+    /// # Fractions of the bounding box of the element referencing the resource
+    ///
+    ObjectBoundingBox,
+}
+
+impl Units {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::UserSpaceOnUse => "userSpaceOnUse",
+            Self::ObjectBoundingBox => "objectBoundingBox",
+        }
+    }
+}
+
+impl Display for Units {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+///
+/// # Build the content of a definition with the `Svg` methods
+///
+/// - `build` The function drawing the content in a started document
+///
+/// # Errors
+///
+/// The first error raised by the drawing methods
+///
+pub(crate) fn content<F: FnOnce(&mut Svg)>(build: F) -> Result<Vec<Child>, SvgError> {
+    let mut svg = Svg::new(Length::default(), Length::default(), ViewBox::default(), "");
+    svg.start();
+    build(&mut svg);
+    svg.into_content()
+}
+
+///
+/// # The kind of a definition drawn with the `Svg` methods
+///
+pub trait Drawing {
+    const TAG: &'static str;
+}
+
+///
+/// # A definition holding elements drawn with the `Svg` methods
+///
+/// The patterns, markers, clip paths and masks share this container, the
+/// attributes of each kind are set by its own methods.
+///
+#[derive(Debug)]
+pub struct Template<K: Drawing> {
+    id: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Child>,
+    error: Option<SvgError>,
+    kind: PhantomData<K>,
+}
+
+impl<K: Drawing> Template<K> {
+    ///
+    /// # An empty definition
+    ///
+    /// - `id` The id of the definition
+    ///
+    pub(crate) fn with_id(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+            error: None,
+            kind: PhantomData,
+        }
+    }
+
+    ///
+    /// # Draw the content
+    ///
+    /// The function receives a started document, its elements become the
+    /// content of the definition. The errors are reported by `Svg::define`.
+    ///
+    /// - `build` The function drawing the content
+    ///
+    #[must_use]
+    pub fn content<F: FnOnce(&mut Svg)>(mut self, build: F) -> Self {
+        match content(build) {
+            Ok(children) => self.children.extend(children),
+            Err(e) => self.error = self.error.or(Some(e)),
+        }
+        self
+    }
+
+    pub(crate) fn attr(mut self, name: &str, value: String) -> Self {
+        match self.attributes.iter_mut().find(|(n, _)| n == name) {
+            Some(attribute) => attribute.1 = value,
+            None => self.attributes.push((name.to_string(), value)),
+        }
+        self
+    }
+}
+
+impl<K: Drawing> Definition for Template<K> {
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn to_node(&self) -> Node {
+        let mut node = Node::new(K::TAG);
+        node.set_attribute("id", self.id.as_str());
+        for (name, value) in &self.attributes {
+            node.set_attribute(name, value);
+        }
+        node.children_mut().extend(self.children.iter().cloned());
+        node
+    }
+
+    fn take_error(&mut self) -> Option<SvgError> {
+        self.error.take()
+    }
+}
@@ -0,0 +1,160 @@
+use crate::creator::color::Color;
+use crate::creator::filter::{
+    ColorInterpolation, ColorMatrix, CompositeOperator, EdgeMode, Filter, Input,
+    MorphologyOperator, TransferFunction,
+};
+use crate::creator::length::Length;
+
+///
+/// # A filter region large enough for the shadows and the glows
+///
+fn spread(filter: Filter) -> Filter {
+    filter.area(
+        Length::percent(-50.0),
+        Length::percent(-50.0),
+        Length::percent(200.0),
+        Length::percent(200.0),
+    )
+}
+
+///
+/// # A blurred shadow shifted under the element
+///
+/// - `id`      The id of the filter
+/// - `dx`      The horizontal shift
+/// - `dy`      The vertical shift
+/// - `blur`    The amount of blur
+/// - `color`   The color of the shadow
+///
+#[must_use]
+pub fn drop_shadow(id: &str, dx: f64, dy: f64, blur: f64, color: Color) -> Filter {
+    spread(Filter::new(id))
+        .gaussian_blur(Input::SourceAlpha, blur)
+        .offset(Input::Previous, dx, dy)
+        .result("shadow")
+        .flood(color)
+        .composite(Input::Previous, "shadow", CompositeOperator::In)
+        .merge(vec![Input::Previous, Input::SourceGraphic])
+}
+
+///
+/// # A blurred halo around the element
+///
+/// - `id`      The id of the filter
+/// - `radius`  The size of the halo
+/// - `color`   The color of the halo
+///
+#[must_use]
+pub fn outer_glow(id: &str, radius: f64, color: Color) -> Filter {
+    spread(Filter::new(id))
+        .morphology(Input::SourceAlpha, MorphologyOperator::Dilate, radius / 2.0)
+        .gaussian_blur(Input::Previous, radius)
+        .result("glow")
+        .flood(color)
+        .composite(Input::Previous, "glow", CompositeOperator::In)
+        .merge(vec![Input::Previous, Input::SourceGraphic])
+}
+
+///
+/// # A blurred shadow inside the element, as if it was engraved
+///
+/// - `id`      The id of the filter
+/// - `dx`      The horizontal shift
+/// - `dy`      The vertical shift
+/// - `blur`    The amount of blur
+/// - `color`   The color of the shadow
+///
+#[must_use]
+pub fn inner_shadow(id: &str, dx: f64, dy: f64, blur: f64, color: Color) -> Filter {
+    Filter::new(id)
+        .flood(color)
+        .composite(Input::Previous, Input::SourceAlpha, CompositeOperator::Out)
+        .offset(Input::Previous, dx, dy)
+        .gaussian_blur(Input::Previous, blur)
+        .composite(Input::Previous, Input::SourceAlpha, CompositeOperator::In)
+        .merge(vec![Input::SourceGraphic, Input::Previous])
+}
+
+///
+/// # A gaussian blur of the element
+///
+/// - `id`      The id of the filter
+/// - `radius`  The amount of blur
+///
+#[must_use]
+pub fn blur(id: &str, radius: f64) -> Filter {
+    Filter::new(id).gaussian_blur(Input::SourceGraphic, radius)
+}
+
+///
+/// # The element in shades of gray
+///
+/// - `id` The id of the filter
+///
+#[must_use]
+pub fn grayscale(id: &str) -> Filter {
+    Filter::new(id)
+        .color_interpolation(ColorInterpolation::Srgb)
+        .color_matrix(Input::SourceGraphic, ColorMatrix::Saturate(0.0))
+}
+
+///
+/// # The element in brown tones, as an old photograph
+///
+/// - `id` The id of the filter
+///
+#[must_use]
+pub fn sepia(id: &str) -> Filter {
+    Filter::new(id)
+        .color_interpolation(ColorInterpolation::Srgb)
+        .color_matrix(
+            Input::SourceGraphic,
+            ColorMatrix::Matrix([
+                0.393, 0.769, 0.189, 0.0, 0.0, //
+                0.349, 0.686, 0.168, 0.0, 0.0, //
+                0.272, 0.534, 0.131, 0.0, 0.0, //
+                0.0, 0.0, 0.0, 1.0, 0.0,
+            ]),
+        )
+}
+
+///
+/// # The element in a gradient of two colors following its luminance
+///
+/// - `id`      The id of the filter
+/// - `dark`    The color of the shadows
+/// - `light`   The color of the highlights
+///
+#[must_use]
+pub fn duotone(id: &str, dark: Color, light: Color) -> Filter {
+    let table = |from: u8, to: u8| {
+        TransferFunction::Table(vec![f64::from(from) / 255.0, f64::from(to) / 255.0])
+    };
+    Filter::new(id)
+        .color_interpolation(ColorInterpolation::Srgb)
+        .color_matrix(Input::SourceGraphic, ColorMatrix::Saturate(0.0))
+        .component_transfer(
+            Input::Previous,
+            &table(dark.r, light.r),
+            &table(dark.g, light.g),
+            &table(dark.b, light.b),
+            &TransferFunction::Identity,
+        )
+}
+
+///
+/// # The element in relief, lit from the top left
+///
+/// - `id` The id of the filter
+///
+#[must_use]
+pub fn emboss(id: &str) -> Filter {
+    Filter::new(id)
+        .color_interpolation(ColorInterpolation::Srgb)
+        .convolve_matrix(
+            Input::SourceGraphic,
+            &[-2.0, -1.0, 0.0, -1.0, 1.0, 1.0, 0.0, 1.0, 2.0],
+            EdgeMode::Duplicate,
+            true,
+        )
+}
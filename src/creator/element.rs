@@ -0,0 +1,833 @@
+use crate::creator::animation::{Animated, Animation};
+use crate::creator::color::Paint;
+use crate::creator::error::SvgError;
+use crate::creator::filter;
+use crate::creator::format::decimal;
+use crate::creator::length::Length;
+use crate::creator::node::Node;
+use crate::creator::path::parse;
+use crate::creator::svg::Svg;
+use crate::creator::transform::Transform;
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+///
+/// # An element kind
+///
+pub trait Kind {
+    ///
+    /// # The element name
+    ///
+    const TAG: &'static str;
+}
+
+///
+/// # A kind accepted as child of the kind `K`
+///
+pub trait ChildOf<K> {}
+
+///
+/// # A kind accepting the presentation attributes
+///
+pub trait Graphic: Kind {}
+
+///
+/// # A kind accepting shapes, groups and texts as children
+///
+pub trait Container: Graphic {}
+
+///
+/// # A kind accepting the `x` and `y` attributes
+///
+pub trait Positioned: Kind {}
+
+///
+/// # A kind accepting the `width` and `height` attributes
+///
+pub trait Dimensioned: Kind {}
+
+///
+/// # A kind accepting the `cx` and `cy` attributes
+///
+pub trait Centered: Kind {}
+
+///
+/// # A kind accepting the `rx` and `ry` attributes
+///
+pub trait Rounded: Kind {}
+
+///
+/// # A kind accepting the `href` attribute
+///
+pub trait Linked: Kind {}
+
+///
+/// # A kind accepting the `points` attribute
+///
+pub trait Pointed: Kind {}
+
+///
+/// # A kind accepting the marker references
+///
+pub trait Marked: Kind {}
+
+///
+/// # A kind accepting a text content
+///
+pub trait Textual: Kind {}
+
+macro_rules! kinds {
+    ($($(#[$doc:meta])* $name:ident => $tag:literal),* $(,)?) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy)]
+            pub struct $name;
+
+            impl Kind for $name {
+                const TAG: &'static str = $tag;
+            }
+        )*
+    };
+}
+
+kinds! {
+    /// # The `<rect>` element
+    Rect => "rect",
+    /// # The `<circle>` element
+    Circle => "circle",
+    /// # The `<ellipse>` element
+    Ellipse => "ellipse",
+    /// # The `<line>` element
+    Line => "line",
+    /// # The `<polyline>` element
+    Polyline => "polyline",
+    /// # The `<polygon>` element
+    Polygon => "polygon",
+    /// # The `<path>` element
+    Path => "path",
+    /// # The `<g>` element
+    Group => "g",
+    /// # The `<a>` element
+    Link => "a",
+    /// # The `<text>` element
+    Text => "text",
+    /// # The `<tspan>` element
+    Tspan => "tspan",
+    /// # The `<use>` element
+    Use => "use",
+    /// # The `<image>` element
+    Image => "image",
+    /// # The `<title>` element
+    Title => "title",
+    /// # The `<animate>` element
+    Animate => "animate",
+}
+
+impl Graphic for Rect {}
+impl Graphic for Circle {}
+impl Graphic for Ellipse {}
+impl Graphic for Line {}
+impl Graphic for Polyline {}
+impl Graphic for Polygon {}
+impl Graphic for Path {}
+impl Graphic for Group {}
+impl Graphic for Link {}
+impl Graphic for Text {}
+impl Graphic for Tspan {}
+impl Graphic for Use {}
+impl Graphic for Image {}
+
+impl Container for Group {}
+impl Container for Link {}
+
+impl Positioned for Rect {}
+impl Positioned for Text {}
+impl Positioned for Tspan {}
+impl Positioned for Use {}
+impl Positioned for Image {}
+
+impl Dimensioned for Rect {}
+impl Dimensioned for Use {}
+impl Dimensioned for Image {}
+
+impl Centered for Circle {}
+impl Centered for Ellipse {}
+
+impl Rounded for Rect {}
+impl Rounded for Ellipse {}
+
+impl Linked for Link {}
+impl Linked for Use {}
+impl Linked for Image {}
+
+impl Pointed for Polyline {}
+impl Pointed for Polygon {}
+
+impl Marked for Line {}
+impl Marked for Polyline {}
+impl Marked for Polygon {}
+impl Marked for Path {}
+
+impl Textual for Text {}
+impl Textual for Tspan {}
+impl Textual for Title {}
+
+impl<K: Container> ChildOf<K> for Rect {}
+impl<K: Container> ChildOf<K> for Circle {}
+impl<K: Container> ChildOf<K> for Ellipse {}
+impl<K: Container> ChildOf<K> for Line {}
+impl<K: Container> ChildOf<K> for Polyline {}
+impl<K: Container> ChildOf<K> for Polygon {}
+impl<K: Container> ChildOf<K> for Path {}
+impl<K: Container> ChildOf<K> for Group {}
+impl<K: Container> ChildOf<K> for Link {}
+impl<K: Container> ChildOf<K> for Text {}
+impl<K: Container> ChildOf<K> for Use {}
+impl<K: Container> ChildOf<K> for Image {}
+impl<K: Graphic> ChildOf<K> for Title {}
+impl<K: Graphic> ChildOf<K> for Animate {}
+impl ChildOf<Text> for Tspan {}
+
+///
+/// # A receiver of closed elements
+///
+pub trait Parent {
+    ///
+    /// # Append a closed element
+    ///
+    /// - `node` The closed element
+    ///
+    fn append(&mut self, node: Node);
+
+    ///
+    /// # Report an error on save
+    ///
+    /// - `error` The error
+    ///
+    fn fail(&mut self, error: SvgError);
+}
+
+///
+/// # The typed entry point of an open svg
+///
+/// Obtained with `Svg::build`, the elements are appended to the element
+/// currently open in the svg.
+///
+#[must_use]
+pub struct Document<'a> {
+    svg: &'a mut Svg,
+}
+
+impl<'a> Document<'a> {
+    pub(crate) fn new(svg: &'a mut Svg) -> Self {
+        Self { svg }
+    }
+
+    ///
+    /// # Return to the string style api
+    ///
+    pub fn done(self) -> &'a mut Svg {
+        self.svg
+    }
+}
+
+impl Parent for Document<'_> {
+    fn append(&mut self, node: Node) {
+        self.svg.append(node);
+    }
+
+    fn fail(&mut self, error: SvgError) {
+        self.svg.fail(error);
+    }
+}
+
+///
+/// # An open element of the kind `K` inside the parent `P`
+///
+#[must_use = "an element is appended to his parent only on close"]
+pub struct Element<P, K> {
+    parent: P,
+    node: Node,
+    kind: PhantomData<K>,
+}
+
+impl<P: Parent, K: Kind> Element<P, K> {
+    fn new(parent: P) -> Self {
+        Self {
+            parent,
+            node: Node::new(K::TAG),
+            kind: PhantomData,
+        }
+    }
+
+    fn attr(mut self, name: &str, value: &str) -> Self {
+        self.node.set_attribute(name, value);
+        self
+    }
+
+    fn reference(self, name: &str, id: &str) -> Self {
+        self.attr(
+            name,
+            format!("url(#{})", id.trim_start_matches('#')).as_str(),
+        )
+    }
+
+    fn paint(mut self, name: &str, paint: Result<Paint, SvgError>) -> Self {
+        let opacity = format!("{name}-opacity");
+        match paint {
+            Ok(p) => match p.opacity() {
+                Some(a) => self
+                    .attr(name, p.to_string().as_str())
+                    .attr(opacity.as_str(), decimal(a, 6).as_str()),
+                None => {
+                    self.node.remove_attribute(opacity.as_str());
+                    self.attr(name, p.to_string().as_str())
+                }
+            },
+            Err(e) => {
+                self.parent.fail(e);
+                self
+            }
+        }
+    }
+
+    ///
+    /// # Close the element
+    ///
+    /// # Return
+    ///
+    /// The parent
+    ///
+    pub fn close(mut self) -> P {
+        self.parent.append(self.node);
+        self.parent
+    }
+
+    ///
+    /// # Start a child
+    ///
+    pub fn child<C: Kind + ChildOf<K>>(self) -> Element<Self, C> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Define an id
+    ///
+    /// - `id` The id name
+    ///
+    pub fn id(self, id: &str) -> Self {
+        self.attr("id", id)
+    }
+
+    ///
+    /// # Add a class
+    ///
+    /// - `class` The class name
+    ///
+    pub fn class(self, class: &str) -> Self {
+        self.attr("class", class)
+    }
+
+    ///
+    /// # Add a style
+    ///
+    /// - `style` The style to add
+    ///
+    pub fn style(self, style: &str) -> Self {
+        self.attr("style", style)
+    }
+}
+
+impl<P: Parent, K: Kind> Parent for Element<P, K> {
+    fn append(&mut self, node: Node) {
+        self.node.append(node);
+    }
+
+    fn fail(&mut self, error: SvgError) {
+        self.parent.fail(error);
+    }
+}
+
+impl<P: Parent, K: Graphic> Element<P, K> {
+    ///
+    /// # Set a fill color
+    ///
+    /// - `fill` The paint, a string is parsed and reported on save when invalid
+    ///
+    pub fn fill<T>(self, fill: T) -> Self
+    where
+        T: TryInto<Paint>,
+        SvgError: From<T::Error>,
+    {
+        self.paint("fill", fill.try_into().map_err(SvgError::from))
+    }
+
+    ///
+    /// # Define a stroke
+    ///
+    /// - `stroke` The paint, a string is parsed and reported on save when invalid
+    ///
+    pub fn stroke<T>(self, stroke: T) -> Self
+    where
+        T: TryInto<Paint>,
+        SvgError: From<T::Error>,
+    {
+        self.paint("stroke", stroke.try_into().map_err(SvgError::from))
+    }
+
+    ///
+    /// # Define a stroke width
+    ///
+    /// - `width`   The stroke width
+    ///
+    pub fn stroke_width<L: Into<Length>>(self, width: L) -> Self {
+        self.attr("stroke-width", width.into().to_string().as_str())
+    }
+
+    ///
+    /// # Add a transformation
+    ///
+    /// - `transform` The transformation, a string or a `Transform`, reported on save when invalid
+    ///
+    pub fn transform<T: Display>(mut self, transform: T) -> Self {
+        let transform = transform.to_string();
+        if let Err(e) = transform.parse::<Transform>() {
+            self.parent.fail(e);
+        }
+        self.attr("transform", transform.as_str())
+    }
+
+    ///
+    /// # Define the opacity
+    ///
+    /// - `opacity` The opacity between 0 and 1
+    ///
+    pub fn opacity(self, opacity: f64) -> Self {
+        self.attr("opacity", opacity.to_string().as_str())
+    }
+
+    ///
+    /// # Draw only the parts inside a clip path
+    ///
+    /// - `id` The id of the clip path
+    ///
+    pub fn clip_path(self, id: &str) -> Self {
+        self.reference("clip-path", id)
+    }
+
+    ///
+    /// # Apply a mask
+    ///
+    /// - `id` The id of the mask
+    ///
+    pub fn mask(self, id: &str) -> Self {
+        self.reference("mask", id)
+    }
+
+    ///
+    /// # Apply a filter
+    ///
+    /// - `f` The id of a filter, `none`, `inherit`, or a filter value as `url(#id)` or `blur(2px)`
+    ///
+    pub fn filter(self, f: &str) -> Self {
+        self.attr("filter", filter::attribute(f).as_str())
+    }
+
+    ///
+    /// # Start a title
+    ///
+    pub fn title(self) -> Element<Self, Title> {
+        self.child()
+    }
+
+    ///
+    /// # Start an animation
+    ///
+    pub fn animate(self) -> Element<Self, Animate> {
+        self.child()
+    }
+
+    ///
+    /// # Add an animation
+    ///
+    /// - `animation` The animate, animateTransform, animateMotion or set
+    ///
+    pub fn animation<A: Animated>(mut self, mut animation: Animation<A>) -> Self {
+        if let Some(error) = animation.take_error() {
+            self.parent.fail(error);
+        }
+        self.node.append(animation.to_node());
+        self
+    }
+}
+
+impl<P: Parent, K: Positioned> Element<P, K> {
+    ///
+    /// # Pos in x
+    ///
+    /// - `x`       The position in x
+    ///
+    pub fn x<L: Into<Length>>(self, x: L) -> Self {
+        self.attr("x", x.into().to_string().as_str())
+    }
+
+    ///
+    /// # Pos in y
+    ///
+    /// - `y`       The position in y
+    ///
+    pub fn y<L: Into<Length>>(self, y: L) -> Self {
+        self.attr("y", y.into().to_string().as_str())
+    }
+}
+
+impl<P: Parent, K: Dimensioned> Element<P, K> {
+    ///
+    /// # Define a width
+    ///
+    /// - `width`   The width
+    ///
+    pub fn width<L: Into<Length>>(self, width: L) -> Self {
+        self.attr("width", width.into().to_string().as_str())
+    }
+
+    ///
+    /// # Define a height
+    ///
+    /// - `height`  The height
+    ///
+    pub fn height<L: Into<Length>>(self, height: L) -> Self {
+        self.attr("height", height.into().to_string().as_str())
+    }
+}
+
+impl<P: Parent, K: Centered> Element<P, K> {
+    ///
+    /// # Defines the x-axis coordinate of the center
+    ///
+    /// - `cx`      The x-axis coordinate
+    ///
+    pub fn cx<L: Into<Length>>(self, cx: L) -> Self {
+        self.attr("cx", cx.into().to_string().as_str())
+    }
+
+    ///
+    /// # Defines the y-axis coordinate of the center
+    ///
+    /// - `cy`      The y-axis coordinate
+    ///
+    pub fn cy<L: Into<Length>>(self, cy: L) -> Self {
+        self.attr("cy", cy.into().to_string().as_str())
+    }
+}
+
+impl<P: Parent, K: Rounded> Element<P, K> {
+    ///
+    /// # Define a radius on the x-axis
+    ///
+    /// - `rx`   The radius
+    ///
+    pub fn rx<L: Into<Length>>(self, rx: L) -> Self {
+        self.attr("rx", rx.into().to_string().as_str())
+    }
+
+    ///
+    /// # Define a radius on the y-axis
+    ///
+    /// - `ry`   The radius
+    ///
+    pub fn ry<L: Into<Length>>(self, ry: L) -> Self {
+        self.attr("ry", ry.into().to_string().as_str())
+    }
+}
+
+impl<P: Parent, K: Linked> Element<P, K> {
+    ///
+    /// # Configure a href
+    ///
+    /// - `href` the href
+    ///
+    pub fn href(self, href: &str) -> Self {
+        self.attr("href", href)
+    }
+}
+
+impl<P: Parent, K: Pointed> Element<P, K> {
+    ///
+    /// # Define the points
+    ///
+    /// - `points` The list of `(x, y)` coordinates
+    ///
+    pub fn points(self, points: &[(f64, f64)]) -> Self {
+        let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        self.attr("points", points.join(" ").as_str())
+    }
+}
+
+impl<P: Parent, K: Marked> Element<P, K> {
+    ///
+    /// # Place a marker on the first vertex
+    ///
+    /// - `id` The id of the marker
+    ///
+    pub fn marker_start(self, id: &str) -> Self {
+        self.reference("marker-start", id)
+    }
+
+    ///
+    /// # Place a marker on the vertices between the first and the last
+    ///
+    /// - `id` The id of the marker
+    ///
+    pub fn marker_mid(self, id: &str) -> Self {
+        self.reference("marker-mid", id)
+    }
+
+    ///
+    /// # Place a marker on the last vertex
+    ///
+    /// - `id` The id of the marker
+    ///
+    pub fn marker_end(self, id: &str) -> Self {
+        self.reference("marker-end", id)
+    }
+}
+
+impl<P: Parent> Element<P, Circle> {
+    ///
+    /// # Define a radius
+    ///
+    /// - `r`       The radius
+    ///
+    pub fn r<L: Into<Length>>(self, r: L) -> Self {
+        self.attr("r", r.into().to_string().as_str())
+    }
+}
+
+impl<P: Parent> Element<P, Line> {
+    ///
+    /// # Define the first point
+    ///
+    /// - `x1` The first x-coordinate
+    /// - `y1` The first y-coordinate
+    ///
+    pub fn from(self, x1: f64, y1: f64) -> Self {
+        self.attr("x1", x1.to_string().as_str())
+            .attr("y1", y1.to_string().as_str())
+    }
+
+    ///
+    /// # Define the second point
+    ///
+    /// - `x2` The second x-coordinate
+    /// - `y2` The second y-coordinate
+    ///
+    pub fn to(self, x2: f64, y2: f64) -> Self {
+        self.attr("x2", x2.to_string().as_str())
+            .attr("y2", y2.to_string().as_str())
+    }
+}
+
+impl<P: Parent> Element<P, Path> {
+    ///
+    /// # Add a data
+    ///
+    /// - `d` The data, a string or a `PathBuilder`, reported on save when invalid
+    ///
+    pub fn d<D: Display>(mut self, d: D) -> Self {
+        let d = d.to_string();
+        if let Err(e) = parse(d.as_str()) {
+            self.parent.fail(e);
+        }
+        self.attr("d", d.as_str())
+    }
+}
+
+impl<P: Parent> Element<P, Link> {
+    ///
+    /// # Add a target
+    ///
+    /// - `target` The target
+    ///
+    pub fn target(self, target: &str) -> Self {
+        self.attr("target", target)
+    }
+}
+
+impl<P: Parent> Element<P, Text> {
+    ///
+    /// # Set the text-anchor position
+    ///
+    /// - `anchor` The position
+    ///
+    pub fn text_anchor(self, anchor: &str) -> Self {
+        self.attr("text-anchor", anchor)
+    }
+
+    ///
+    /// # Add a font size
+    ///
+    /// - `size` The font size
+    ///
+    pub fn font_size<L: Into<Length>>(self, size: L) -> Self {
+        self.attr("font-size", size.into().to_string().as_str())
+    }
+
+    ///
+    /// # Add a font family
+    ///
+    /// - `family` The font
+    ///
+    pub fn font_family(self, family: &str) -> Self {
+        self.attr("font-family", family)
+    }
+
+    ///
+    /// # Start a tspan
+    ///
+    pub fn tspan(self) -> Element<Self, Tspan> {
+        self.child()
+    }
+}
+
+impl<P: Parent, K: Textual> Element<P, K> {
+    ///
+    /// # Add a text
+    ///
+    /// - `data` The text, escaped at the serialization
+    ///
+    pub fn content(mut self, data: &str) -> Self {
+        self.node.append_text(data);
+        self
+    }
+}
+
+impl<P: Parent> Element<P, Animate> {
+    ///
+    /// # Set the attribute name
+    ///
+    /// - `name` The name
+    ///
+    pub fn attribute_name(self, name: &str) -> Self {
+        self.attr("attributeName", name)
+    }
+
+    ///
+    /// # Set the values
+    ///
+    /// - `values` The values separated by `;`
+    ///
+    pub fn values(self, values: &str) -> Self {
+        self.attr("values", values)
+    }
+
+    ///
+    /// # Set a duration
+    ///
+    /// - `duration` The duration
+    ///
+    pub fn dur(self, duration: &str) -> Self {
+        self.attr("dur", duration)
+    }
+
+    ///
+    /// # Set the repeat count
+    ///
+    /// - `count` The value
+    ///
+    pub fn repeat_count(self, count: &str) -> Self {
+        self.attr("repeatCount", count)
+    }
+}
+
+///
+/// # The elements allowed inside the svg and the containers
+///
+pub trait Shapes: Parent + Sized {
+    ///
+    /// # Start a rectangle
+    ///
+    fn rect(self) -> Element<Self, Rect> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start a circle
+    ///
+    fn circle(self) -> Element<Self, Circle> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start an ellipse
+    ///
+    fn ellipse(self) -> Element<Self, Ellipse> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start a line
+    ///
+    fn line(self) -> Element<Self, Line> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start a polyline
+    ///
+    fn polyline(self) -> Element<Self, Polyline> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start a polygon
+    ///
+    fn polygon(self) -> Element<Self, Polygon> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start a path
+    ///
+    fn path(self) -> Element<Self, Path> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start a group
+    ///
+    fn g(self) -> Element<Self, Group> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start a link
+    ///
+    fn a(self) -> Element<Self, Link> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start a text
+    ///
+    fn text(self) -> Element<Self, Text> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start a use
+    ///
+    fn u(self) -> Element<Self, Use> {
+        Element::new(self)
+    }
+
+    ///
+    /// # Start an image
+    ///
+    fn image(self) -> Element<Self, Image> {
+        Element::new(self)
+    }
+}
+
+impl Shapes for Document<'_> {}
+impl<P: Parent, K: Container> Shapes for Element<P, K> {}
@@ -0,0 +1,174 @@
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+///
+/// # The errors of the svg creation
+///
+#[derive(Debug)]
+pub enum SvgError {
+    ///
+    /// # A file system failure
+    ///
+    Io(io::Error),
+    ///
+    /// # The generated document is not valid
+    ///
+    /// - `line`    The line of the first error
+    /// - `column`  The column of the first error
+    /// - `message` The error description
+    ///
+    Validation {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    ///
+    /// # An external program is not installed
+    ///
+    MissingTool(String),
+    ///
+    /// # An external program has been killed or ended unexpectedly
+    ///
+    ToolFailed { tool: String, code: Option<i32> },
+    ///
+    /// # The image format is not png, jpeg, gif, webp or svg
+    ///
+    UnsupportedImage,
+    ///
+    /// # A resource can not be resolved by the fetcher
+    ///
+    ResourceNotFound(String),
+    ///
+    /// # A length or an unit can not be parsed
+    ///
+    InvalidLength(String),
+    ///
+    /// # A color or a paint can not be parsed
+    ///
+    InvalidColor(String),
+    ///
+    /// # The path data is malformed
+    ///
+    /// - `offset`  The byte offset of the error
+    /// - `message` The error description
+    ///
+    InvalidPath { offset: usize, message: String },
+    ///
+    /// # A transform list can not be parsed
+    ///
+    InvalidTransform(String),
+    ///
+    /// # A view box or an aspect ratio can not be parsed
+    ///
+    InvalidViewBox(String),
+    ///
+    /// # A filter primitive is malformed or uses an unknown result
+    ///
+    InvalidFilter(String),
+    ///
+    /// # An animation is inconsistent
+    ///
+    InvalidAnimation(String),
+    ///
+    /// # An element, an attribute or a text is added before the call of `start`
+    ///
+    NotStarted(String),
+    ///
+    /// # An id is already used by another element of the document
+    ///
+    DuplicateId(String),
+}
+
+impl Display for SvgError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "io error: {e}"),
+            Self::Validation {
+                line,
+                column,
+                message,
+            } => write!(f, "invalid svg at {line}:{column}: {message}"),
+            Self::MissingTool(tool) => write!(f, "{tool} not found"),
+            Self::ToolFailed { tool, code } => match code {
+                Some(c) => write!(f, "{tool} exited with the code {c}"),
+                None => write!(f, "{tool} has been terminated"),
+            },
+            Self::UnsupportedImage => write!(f, "unsupported image format"),
+            Self::ResourceNotFound(uri) => write!(f, "resource {uri} not found"),
+            Self::InvalidLength(length) => write!(f, "invalid length {length}"),
+            Self::InvalidColor(color) => write!(f, "invalid color {color}"),
+            Self::InvalidPath { offset, message } => {
+                write!(f, "invalid path data at {offset}: {message}")
+            }
+            Self::InvalidTransform(transform) => write!(f, "invalid transform {transform}"),
+            Self::InvalidViewBox(view_box) => write!(f, "invalid view box {view_box}"),
+            Self::InvalidFilter(message) => write!(f, "invalid filter: {message}"),
+            Self::InvalidAnimation(message) => write!(f, "invalid animation: {message}"),
+            Self::NotStarted(name) => write!(f, "{name} added before the start of the svg"),
+            Self::DuplicateId(id) => write!(f, "the id {id} is already used"),
+        }
+    }
+}
+
+impl Clone for SvgError {
+    ///
+    /// # A copy of the error
+    ///
+    /// An io error is copied with its kind and its message.
+    ///
+    fn clone(&self) -> Self {
+        match self {
+            Self::Io(e) => Self::Io(io::Error::new(e.kind(), e.to_string())),
+            Self::Validation {
+                line,
+                column,
+                message,
+            } => Self::Validation {
+                line: *line,
+                column: *column,
+                message: message.clone(),
+            },
+            Self::MissingTool(tool) => Self::MissingTool(tool.clone()),
+            Self::ToolFailed { tool, code } => Self::ToolFailed {
+                tool: tool.clone(),
+                code: *code,
+            },
+            Self::UnsupportedImage => Self::UnsupportedImage,
+            Self::ResourceNotFound(uri) => Self::ResourceNotFound(uri.clone()),
+            Self::InvalidLength(length) => Self::InvalidLength(length.clone()),
+            Self::InvalidColor(color) => Self::InvalidColor(color.clone()),
+            Self::InvalidPath { offset, message } => Self::InvalidPath {
+                offset: *offset,
+                message: message.clone(),
+            },
+            Self::InvalidTransform(transform) => Self::InvalidTransform(transform.clone()),
+            Self::InvalidViewBox(view_box) => Self::InvalidViewBox(view_box.clone()),
+            Self::InvalidFilter(message) => Self::InvalidFilter(message.clone()),
+            Self::InvalidAnimation(message) => Self::InvalidAnimation(message.clone()),
+            Self::NotStarted(name) => Self::NotStarted(name.clone()),
+            Self::DuplicateId(id) => Self::DuplicateId(id.clone()),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Infallible> for SvgError {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
+impl From<io::Error> for SvgError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
@@ -0,0 +1,200 @@
+use crate::creator::error::SvgError;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+///
+/// # The environment variable defining the default cache directory
+///
+pub const CACHE_VAR: &str = "SVG_CREATOR_CACHE";
+
+///
+/// # A source of the remote resources used by `Svg::image`
+///
+pub trait ResourceFetcher {
+    ///
+    /// # Get the content of a resource
+    ///
+    /// - `uri` The resource uri
+    ///
+    /// # Errors
+    ///
+    /// When the resource can not be resolved
+    ///
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, SvgError>;
+}
+
+impl<F> ResourceFetcher for F
+where
+    F: Fn(&str) -> Result<Vec<u8>, SvgError>,
+{
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, SvgError> {
+        self(uri)
+    }
+}
+
+impl<F: ResourceFetcher + ?Sized> ResourceFetcher for Arc<F> {
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, SvgError> {
+        self.as_ref().fetch(uri)
+    }
+}
+
+///
+/// # The default fetcher, without network access
+///
+/// A resource is searched in memory, then in the cache directory. In the
+/// cache, `https://simpleicons.org/icons/rust.svg` is resolved to
+/// `simpleicons.org/icons/rust.svg` then to `rust.svg`.
+///
+#[derive(Debug, Clone)]
+pub struct OfflineFetcher {
+    resources: HashMap<String, Vec<u8>>,
+    cache: Option<PathBuf>,
+}
+
+impl OfflineFetcher {
+    ///
+    /// # Constructor
+    ///
+    /// A fetcher without resources and without cache
+    ///
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            resources: HashMap::new(),
+            cache: None,
+        }
+    }
+
+    ///
+    /// # Register a resource in memory
+    ///
+    /// - `uri`     The resource uri
+    /// - `data`    The resource content
+    ///
+    #[must_use]
+    pub fn insert(mut self, uri: &str, data: &[u8]) -> Self {
+        self.resources.insert(uri.to_string(), data.to_vec());
+        self
+    }
+
+    ///
+    /// # Define the cache directory
+    ///
+    /// - `dir` The cache directory
+    ///
+    #[must_use]
+    pub fn cache<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.cache = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    fn candidates(dir: &Path, uri: &str) -> Vec<PathBuf> {
+        let location = uri.split_once("://").map_or(uri, |(_, l)| l);
+        let location = location.split(['?', '#']).next().unwrap_or_default();
+        let segments: Vec<&str> = location
+            .split('/')
+            .filter(|s| !s.is_empty() && *s != "." && *s != "..")
+            .collect();
+        let mut candidates = Vec::new();
+        if !segments.is_empty() {
+            candidates.push(segments.iter().fold(dir.to_path_buf(), |p, s| p.join(s)));
+        }
+        if let Some(name) = segments.last().filter(|_| segments.len() > 1) {
+            candidates.push(dir.join(name));
+        }
+        candidates
+    }
+}
+
+impl Default for OfflineFetcher {
+    ///
+    /// # A fetcher using the cache directory defined by `SVG_CREATOR_CACHE`
+    ///
+    fn default() -> Self {
+        let fetcher = Self::new();
+        match env::var_os(CACHE_VAR) {
+            Some(dir) => fetcher.cache(dir),
+            None => fetcher,
+        }
+    }
+}
+
+impl ResourceFetcher for OfflineFetcher {
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, SvgError> {
+        if let Some(data) = self.resources.get(uri) {
+            return Ok(data.clone());
+        }
+        if let Some(dir) = &self.cache {
+            for candidate in Self::candidates(dir, uri) {
+                if candidate.is_file() {
+                    return Ok(fs::read(candidate)?);
+                }
+            }
+        }
+        Err(SvgError::ResourceNotFound(uri.to_string()))
+    }
+}
+
+///
+/// # A fetcher reading `file://` uris and paths
+///
+/// The relative paths are resolved from the root directory.
+///
+#[derive(Debug, Clone)]
+pub struct FileFetcher {
+    root: PathBuf,
+}
+
+impl FileFetcher {
+    ///
+    /// # Constructor
+    ///
+    /// - `root` The directory of the relative paths
+    ///
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl ResourceFetcher for FileFetcher {
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, SvgError> {
+        if uri.contains("://") && !uri.starts_with("file://") {
+            return Err(SvgError::ResourceNotFound(uri.to_string()));
+        }
+        let path = self.root.join(uri.trim_start_matches("file://"));
+        fs::read(path).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => SvgError::ResourceNotFound(uri.to_string()),
+            _ => SvgError::Io(e),
+        })
+    }
+}
+
+///
+/// # A fetcher downloading with wget
+///
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WgetFetcher;
+
+impl ResourceFetcher for WgetFetcher {
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>, SvgError> {
+        match Command::new("wget").arg("-qO-").arg(uri).output() {
+            Ok(output) if output.status.success() => Ok(output.stdout),
+            Ok(output) => Err(SvgError::ToolFailed {
+                tool: "wget".to_string(),
+                code: output.status.code(),
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(SvgError::MissingTool("wget".to_string()))
+            }
+            Err(e) => Err(SvgError::Io(e)),
+        }
+    }
+}
@@ -0,0 +1,1058 @@
+use crate::creator::color::Color;
+use crate::creator::definition::{Definition, Units};
+use crate::creator::error::SvgError;
+use crate::creator::format::decimal;
+use crate::creator::length::Length;
+use crate::creator::node::Node;
+use std::fmt::{Display, Formatter};
+
+///
+/// # The image read by a filter primitive
+///
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Input {
+    ///
+    /// # The result of the previous primitive, or the source for the first one
+    ///
+    #[default]
+    Previous,
+    ///
+    /// # The element referencing the filter
+    ///
+    SourceGraphic,
+    ///
+    /// # The alpha channel of the element referencing the filter
+    ///
+    SourceAlpha,
+    ///
+    /// # The canvas under the filter region
+    ///
+    BackgroundImage,
+    ///
+    /// # The alpha channel of the canvas under the filter region
+    ///
+    BackgroundAlpha,
+    ///
+    /// # The fill of the element referencing the filter
+    ///
+    FillPaint,
+    ///
+    /// # The stroke of the element referencing the filter
+    ///
+    StrokePaint,
+    ///
+    /// # The result named by a previous primitive
+    ///
+    Result(String),
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Previous => Ok(()),
+            Self::SourceGraphic => f.write_str("SourceGraphic"),
+            Self::SourceAlpha => f.write_str("SourceAlpha"),
+            Self::BackgroundImage => f.write_str("BackgroundImage"),
+            Self::BackgroundAlpha => f.write_str("BackgroundAlpha"),
+            Self::FillPaint => f.write_str("FillPaint"),
+            Self::StrokePaint => f.write_str("StrokePaint"),
+            Self::Result(name) => f.write_str(name),
+        }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(value: &str) -> Self {
+        match value {
+            "" => Self::Previous,
+            "SourceGraphic" => Self::SourceGraphic,
+            "SourceAlpha" => Self::SourceAlpha,
+            "BackgroundImage" => Self::BackgroundImage,
+            "BackgroundAlpha" => Self::BackgroundAlpha,
+            "FillPaint" => Self::FillPaint,
+            "StrokePaint" => Self::StrokePaint,
+            name => Self::Result(name.to_string()),
+        }
+    }
+}
+
+///
+/// # The mode of `feBlend`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Darken,
+    Lighten,
+}
+
+impl BlendMode {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Multiply => "multiply",
+            Self::Screen => "screen",
+            Self::Darken => "darken",
+            Self::Lighten => "lighten",
+        }
+    }
+}
+
+///
+/// # The operator of `feComposite`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CompositeOperator {
+    ///
+    /// # The first input over the second
+    ///
+    #[default]
+    Over,
+    ///
+    /// # The first input where the second is opaque
+    ///
+    In,
+    ///
+    /// # The first input where the second is transparent
+    ///
+    Out,
+    ///
+    /// # The first input over the second, where the second is opaque
+    ///
+    Atop,
+    ///
+    /// # The inputs where the other one is transparent
+    ///
+    Xor,
+    ///
+    /// # `k1 × i1 × i2 + k2 × i1 + k3 × i2 + k4` for each channel
+    ///
+    Arithmetic { k1: f64, k2: f64, k3: f64, k4: f64 },
+}
+
+impl CompositeOperator {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Over => "over",
+            Self::In => "in",
+            Self::Out => "out",
+            Self::Atop => "atop",
+            Self::Xor => "xor",
+            Self::Arithmetic { .. } => "arithmetic",
+        }
+    }
+}
+
+///
+/// # The transformation of `feColorMatrix`
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorMatrix {
+    ///
+    /// # A 4×5 matrix applied to the rgba values, row by row
+    ///
+    Matrix([f64; 20]),
+    ///
+    /// # Scale the saturation, 0 is gray and 1 unchanged
+    ///
+    Saturate(f64),
+    ///
+    /// # Rotate the hue by an angle in degrees
+    ///
+    HueRotate(f64),
+    ///
+    /// # Convert the luminance into the alpha channel
+    ///
+    LuminanceToAlpha,
+}
+
+impl ColorMatrix {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Matrix(_) => "matrix",
+            Self::Saturate(_) => "saturate",
+            Self::HueRotate(_) => "hueRotate",
+            Self::LuminanceToAlpha => "luminanceToAlpha",
+        }
+    }
+
+    fn values(&self) -> Option<String> {
+        match self {
+            Self::Matrix(values) => Some(list(values)),
+            Self::Saturate(value) | Self::HueRotate(value) => Some(decimal(*value, 6)),
+            Self::LuminanceToAlpha => None,
+        }
+    }
+}
+
+///
+/// # A channel function of `feComponentTransfer`
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum TransferFunction {
+    ///
+    /// # Keep the channel unchanged
+    ///
+    #[default]
+    Identity,
+    ///
+    /// # Interpolate linearly between the values
+    ///
+    Table(Vec<f64>),
+    ///
+    /// # Map the channel to steps of the values
+    ///
+    Discrete(Vec<f64>),
+    ///
+    /// # `slope × c + intercept`
+    ///
+    Linear { slope: f64, intercept: f64 },
+    ///
+    /// # `amplitude × c ^ exponent + offset`
+    ///
+    Gamma {
+        amplitude: f64,
+        exponent: f64,
+        offset: f64,
+    },
+}
+
+impl TransferFunction {
+    fn to_node(&self, channel: char) -> Option<Node> {
+        let mut node = Node::new(format!("feFunc{channel}").as_str());
+        match self {
+            Self::Identity => return None,
+            Self::Table(values) => node
+                .set_attribute("type", "table")
+                .set_attribute("tableValues", list(values).as_str()),
+            Self::Discrete(values) => node
+                .set_attribute("type", "discrete")
+                .set_attribute("tableValues", list(values).as_str()),
+            Self::Linear { slope, intercept } => node
+                .set_attribute("type", "linear")
+                .set_attribute("slope", decimal(*slope, 6).as_str())
+                .set_attribute("intercept", decimal(*intercept, 6).as_str()),
+            Self::Gamma {
+                amplitude,
+                exponent,
+                offset,
+            } => node
+                .set_attribute("type", "gamma")
+                .set_attribute("amplitude", decimal(*amplitude, 6).as_str())
+                .set_attribute("exponent", decimal(*exponent, 6).as_str())
+                .set_attribute("offset", decimal(*offset, 6).as_str()),
+        };
+        Some(node)
+    }
+}
+
+///
+/// # The operator of `feMorphology`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MorphologyOperator {
+    ///
+    /// # Thin the shapes
+    ///
+    #[default]
+    Erode,
+    ///
+    /// # Fatten the shapes
+    ///
+    Dilate,
+}
+
+impl MorphologyOperator {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Erode => "erode",
+            Self::Dilate => "dilate",
+        }
+    }
+}
+
+///
+/// # The noise of `feTurbulence`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TurbulenceType {
+    ///
+    /// # A smooth noise, as clouds
+    ///
+    FractalNoise,
+    ///
+    /// # A noise with sharp ridges, as marble or fire
+    ///
+    #[default]
+    Turbulence,
+}
+
+impl TurbulenceType {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::FractalNoise => "fractalNoise",
+            Self::Turbulence => "turbulence",
+        }
+    }
+}
+
+///
+/// # A color channel read by `feDisplacementMap`
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Channel {
+    R,
+    G,
+    B,
+    #[default]
+    A,
+}
+
+impl Channel {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::R => "R",
+            Self::G => "G",
+            Self::B => "B",
+            Self::A => "A",
+        }
+    }
+}
+
+///
+/// # How `feConvolveMatrix` extends the image at the edges
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgeMode {
+    ///
+    /// # Repeat the pixels of the edges
+    ///
+    #[default]
+    Duplicate,
+    ///
+    /// # Take the pixels of the opposite edge
+    ///
+    Wrap,
+    ///
+    /// # Transparent black pixels
+    ///
+    None,
+}
+
+impl EdgeMode {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate",
+            Self::Wrap => "wrap",
+            Self::None => "none",
+        }
+    }
+}
+
+///
+/// # The light source of the lighting primitives
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Light {
+    ///
+    /// # An infinitely distant light
+    ///
+    /// - `azimuth`     The direction in the xy plane, in degrees
+    /// - `elevation`   The angle above the xy plane, in degrees
+    ///
+    Distant { azimuth: f64, elevation: f64 },
+    ///
+    /// # A light radiating from a point
+    ///
+    Point { x: f64, y: f64, z: f64 },
+    ///
+    /// # A light radiating from a point toward another point
+    ///
+    /// - `at`          The point the light is pointed at
+    /// - `exponent`    The focus of the light
+    /// - `cone`        The maximal angle to the axis of the light, in degrees
+    ///
+    Spot {
+        x: f64,
+        y: f64,
+        z: f64,
+        at: (f64, f64, f64),
+        exponent: f64,
+        cone: Option<f64>,
+    },
+}
+
+impl Light {
+    fn to_node(self) -> Node {
+        let number = |value: f64| decimal(value, 6);
+        match self {
+            Self::Distant { azimuth, elevation } => {
+                let mut node = Node::new("feDistantLight");
+                node.set_attribute("azimuth", number(azimuth).as_str())
+                    .set_attribute("elevation", number(elevation).as_str());
+                node
+            }
+            Self::Point { x, y, z } => {
+                let mut node = Node::new("fePointLight");
+                node.set_attribute("x", number(x).as_str())
+                    .set_attribute("y", number(y).as_str())
+                    .set_attribute("z", number(z).as_str());
+                node
+            }
+            Self::Spot {
+                x,
+                y,
+                z,
+                at,
+                exponent,
+                cone,
+            } => {
+                let mut node = Node::new("feSpotLight");
+                node.set_attribute("x", number(x).as_str())
+                    .set_attribute("y", number(y).as_str())
+                    .set_attribute("z", number(z).as_str())
+                    .set_attribute("pointsAtX", number(at.0).as_str())
+                    .set_attribute("pointsAtY", number(at.1).as_str())
+                    .set_attribute("pointsAtZ", number(at.2).as_str())
+                    .set_attribute("specularExponent", number(exponent).as_str());
+                if let Some(cone) = cone {
+                    node.set_attribute("limitingConeAngle", number(cone).as_str());
+                }
+                node
+            }
+        }
+    }
+}
+
+///
+/// # The color space of the filter operations
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorInterpolation {
+    ///
+    /// # The gamma encoded colors
+    ///
+    Srgb,
+    ///
+    /// # The linear colors
+    ///
+    #[default]
+    LinearRgb,
+}
+
+impl ColorInterpolation {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Srgb => "sRGB",
+            Self::LinearRgb => "linearRGB",
+        }
+    }
+}
+
+///
+/// # A filter definition
+///
+/// The primitives are applied in order, each one reads the result of the
+/// previous one unless another input is given. A primitive is named with
+/// `result` and read later with `Input::Result` or its name.
+///
+#[derive(Debug)]
+pub struct Filter {
+    id: String,
+    attributes: Vec<(String, String)>,
+    primitives: Vec<Node>,
+    results: Vec<String>,
+    error: Option<SvgError>,
+}
+
+impl Filter {
+    ///
+    /// # Constructor
+    ///
+    /// - `id` The id of the filter
+    ///
+    #[must_use]
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            attributes: Vec::new(),
+            primitives: Vec::new(),
+            results: Vec::new(),
+            error: None,
+        }
+    }
+
+    ///
+    /// # Define the region where the filter is drawn
+    ///
+    /// - `x`       The left, -10% when not defined
+    /// - `y`       The top, -10% when not defined
+    /// - `width`   The width, 120% when not defined
+    /// - `height`  The height, 120% when not defined
+    ///
+    #[must_use]
+    pub fn area<X, Y, W, H>(mut self, x: X, y: Y, width: W, height: H) -> Self
+    where
+        X: Into<Length>,
+        Y: Into<Length>,
+        W: Into<Length>,
+        H: Into<Length>,
+    {
+        self.attr("x", x.into().to_string());
+        self.attr("y", y.into().to_string());
+        self.attr("width", width.into().to_string());
+        self.attr("height", height.into().to_string());
+        self
+    }
+
+    ///
+    /// # Define the coordinate system of the region
+    ///
+    /// - `units` The units, `ObjectBoundingBox` when not defined
+    ///
+    #[must_use]
+    pub fn units(mut self, units: Units) -> Self {
+        self.attr("filterUnits", units.to_string());
+        self
+    }
+
+    ///
+    /// # Define the coordinate system of the primitive attributes
+    ///
+    /// - `units` The units, `UserSpaceOnUse` when not defined
+    ///
+    #[must_use]
+    pub fn primitive_units(mut self, units: Units) -> Self {
+        self.attr("primitiveUnits", units.to_string());
+        self
+    }
+
+    ///
+    /// # Define the color space of the operations
+    ///
+    /// - `space` The color space, `LinearRgb` when not defined
+    ///
+    #[must_use]
+    pub fn color_interpolation(mut self, space: ColorInterpolation) -> Self {
+        self.attr("color-interpolation-filters", space.as_str().to_string());
+        self
+    }
+
+    ///
+    /// # Name the result of the last primitive
+    ///
+    /// - `name` The name read by the next primitives
+    ///
+    #[must_use]
+    pub fn result(mut self, name: &str) -> Self {
+        match self.primitives.last_mut() {
+            Some(primitive) => {
+                primitive.set_attribute("result", name);
+                self.results.push(name.to_string());
+            }
+            None => self.fail(format!("no primitive to name {name}")),
+        }
+        self
+    }
+
+    ///
+    /// # Blur the input (`feGaussianBlur`)
+    ///
+    /// - `input`           The image to blur
+    /// - `std_deviation`   The amount of blur
+    ///
+    #[must_use]
+    pub fn gaussian_blur<I: Into<Input>>(self, input: I, std_deviation: f64) -> Self {
+        let mut node = Node::new("feGaussianBlur");
+        node.set_attribute("stdDeviation", decimal(std_deviation, 6).as_str());
+        self.primitive(node, [("in", input.into())])
+    }
+
+    ///
+    /// # Move the input (`feOffset`)
+    ///
+    /// - `input`   The image to move
+    /// - `dx`      The horizontal shift
+    /// - `dy`      The vertical shift
+    ///
+    #[must_use]
+    pub fn offset<I: Into<Input>>(self, input: I, dx: f64, dy: f64) -> Self {
+        let mut node = Node::new("feOffset");
+        node.set_attribute("dx", decimal(dx, 6).as_str())
+            .set_attribute("dy", decimal(dy, 6).as_str());
+        self.primitive(node, [("in", input.into())])
+    }
+
+    ///
+    /// # Fill the region with a color (`feFlood`)
+    ///
+    /// - `color` The color
+    ///
+    #[must_use]
+    pub fn flood(self, color: Color) -> Self {
+        let mut node = Node::new("feFlood");
+        paint(&mut node, "flood", color);
+        self.primitive(node, [])
+    }
+
+    ///
+    /// # Combine two inputs with a blend mode (`feBlend`)
+    ///
+    /// - `input`   The top image
+    /// - `input2`  The bottom image
+    /// - `mode`    The blend mode
+    ///
+    #[must_use]
+    pub fn blend<I: Into<Input>, J: Into<Input>>(
+        self,
+        input: I,
+        input2: J,
+        mode: BlendMode,
+    ) -> Self {
+        let mut node = Node::new("feBlend");
+        node.set_attribute("mode", mode.as_str());
+        self.primitive(node, [("in", input.into()), ("in2", input2.into())])
+    }
+
+    ///
+    /// # Combine two inputs with a Porter-Duff operator (`feComposite`)
+    ///
+    /// - `input`       The first image
+    /// - `input2`      The second image
+    /// - `operator`    The operator
+    ///
+    #[must_use]
+    pub fn composite<I, J>(self, input: I, input2: J, operator: CompositeOperator) -> Self
+    where
+        I: Into<Input>,
+        J: Into<Input>,
+    {
+        let mut node = Node::new("feComposite");
+        node.set_attribute("operator", operator.as_str());
+        if let CompositeOperator::Arithmetic { k1, k2, k3, k4 } = operator {
+            node.set_attribute("k1", decimal(k1, 6).as_str())
+                .set_attribute("k2", decimal(k2, 6).as_str())
+                .set_attribute("k3", decimal(k3, 6).as_str())
+                .set_attribute("k4", decimal(k4, 6).as_str());
+        }
+        self.primitive(node, [("in", input.into()), ("in2", input2.into())])
+    }
+
+    ///
+    /// # Stack the inputs, the first one at the bottom (`feMerge`)
+    ///
+    /// - `inputs` The images
+    ///
+    #[must_use]
+    pub fn merge<I: Into<Input>>(mut self, inputs: Vec<I>) -> Self {
+        let mut node = Node::new("feMerge");
+        for input in inputs {
+            let input = input.into();
+            self.check(&input);
+            let mut merge = Node::new("feMergeNode");
+            if input != Input::Previous {
+                merge.set_attribute("in", input.to_string().as_str());
+            }
+            node.append(merge);
+        }
+        self.primitive(node, [])
+    }
+
+    ///
+    /// # Transform the colors (`feColorMatrix`)
+    ///
+    /// - `input`   The image
+    /// - `matrix`  The transformation
+    ///
+    #[must_use]
+    pub fn color_matrix<I: Into<Input>>(self, input: I, matrix: ColorMatrix) -> Self {
+        let mut node = Node::new("feColorMatrix");
+        node.set_attribute("type", matrix.as_str());
+        if let Some(values) = matrix.values() {
+            node.set_attribute("values", values.as_str());
+        }
+        self.primitive(node, [("in", input.into())])
+    }
+
+    ///
+    /// # Remap each channel (`feComponentTransfer`)
+    ///
+    /// - `input`   The image
+    /// - `red`     The function of the red channel
+    /// - `green`   The function of the green channel
+    /// - `blue`    The function of the blue channel
+    /// - `alpha`   The function of the alpha channel
+    ///
+    #[must_use]
+    pub fn component_transfer<I: Into<Input>>(
+        self,
+        input: I,
+        red: &TransferFunction,
+        green: &TransferFunction,
+        blue: &TransferFunction,
+        alpha: &TransferFunction,
+    ) -> Self {
+        let mut node = Node::new("feComponentTransfer");
+        for (function, channel) in [(red, 'R'), (green, 'G'), (blue, 'B'), (alpha, 'A')] {
+            if let Some(function) = function.to_node(channel) {
+                node.append(function);
+            }
+        }
+        self.primitive(node, [("in", input.into())])
+    }
+
+    ///
+    /// # Apply a square convolution kernel (`feConvolveMatrix`)
+    ///
+    /// The divisor is the sum of the kernel, or 1 when the sum is 0.
+    ///
+    /// - `input`           The image
+    /// - `kernel`          The values row by row, their count must be a square
+    /// - `edge`            How the image is extended at the edges
+    /// - `preserve_alpha`  Apply the kernel on the colors only
+    ///
+    #[must_use]
+    pub fn convolve_matrix<I: Into<Input>>(
+        mut self,
+        input: I,
+        kernel: &[f64],
+        edge: EdgeMode,
+        preserve_alpha: bool,
+    ) -> Self {
+        let order = (1..=kernel.len())
+            .find(|o| o * o >= kernel.len())
+            .unwrap_or(0);
+        if order == 0 || order * order != kernel.len() {
+            self.fail(format!("a kernel of {} values is not square", kernel.len()));
+        }
+        let mut node = Node::new("feConvolveMatrix");
+        node.set_attribute("order", order.to_string().as_str())
+            .set_attribute("kernelMatrix", list(kernel).as_str())
+            .set_attribute("edgeMode", edge.as_str());
+        if preserve_alpha {
+            node.set_attribute("preserveAlpha", "true");
+        }
+        self.primitive(node, [("in", input.into())])
+    }
+
+    ///
+    /// # Thin or fatten the shapes (`feMorphology`)
+    ///
+    /// - `input`       The image
+    /// - `operator`    Erode or dilate
+    /// - `radius`      The size of the operation
+    ///
+    #[must_use]
+    pub fn morphology<I: Into<Input>>(
+        self,
+        input: I,
+        operator: MorphologyOperator,
+        radius: f64,
+    ) -> Self {
+        let mut node = Node::new("feMorphology");
+        node.set_attribute("operator", operator.as_str())
+            .set_attribute("radius", decimal(radius, 6).as_str());
+        self.primitive(node, [("in", input.into())])
+    }
+
+    ///
+    /// # Generate a Perlin noise (`feTurbulence`)
+    ///
+    /// - `kind`        The noise
+    /// - `frequency`   The base frequency in x and y
+    /// - `octaves`     The number of noise functions
+    /// - `seed`        The seed of the random numbers
+    /// - `stitch`      Make the noise tileable
+    ///
+    #[must_use]
+    pub fn turbulence(
+        self,
+        kind: TurbulenceType,
+        frequency: (f64, f64),
+        octaves: u32,
+        seed: f64,
+        stitch: bool,
+    ) -> Self {
+        let mut node = Node::new("feTurbulence");
+        node.set_attribute("type", kind.as_str())
+            .set_attribute("baseFrequency", list(&[frequency.0, frequency.1]).as_str())
+            .set_attribute("numOctaves", octaves.to_string().as_str())
+            .set_attribute("seed", decimal(seed, 6).as_str());
+        if stitch {
+            node.set_attribute("stitchTiles", "stitch");
+        }
+        self.primitive(node, [])
+    }
+
+    ///
+    /// # Move the pixels of an input by the channels of another (`feDisplacementMap`)
+    ///
+    /// - `input`   The image to distort
+    /// - `map`     The image read as displacement
+    /// - `scale`   The maximal displacement
+    /// - `x`       The channel of the horizontal displacement
+    /// - `y`       The channel of the vertical displacement
+    ///
+    #[must_use]
+    pub fn displacement_map<I, J>(
+        self,
+        input: I,
+        map: J,
+        scale: f64,
+        x: Channel,
+        y: Channel,
+    ) -> Self
+    where
+        I: Into<Input>,
+        J: Into<Input>,
+    {
+        let mut node = Node::new("feDisplacementMap");
+        node.set_attribute("scale", decimal(scale, 6).as_str())
+            .set_attribute("xChannelSelector", x.as_str())
+            .set_attribute("yChannelSelector", y.as_str());
+        self.primitive(node, [("in", input.into()), ("in2", map.into())])
+    }
+
+    ///
+    /// # Light the alpha channel as a matte surface (`feDiffuseLighting`)
+    ///
+    /// - `input`       The bump map
+    /// - `color`       The color of the light
+    /// - `surface`     The height of the surface for an opaque pixel
+    /// - `constant`    The diffuse reflection constant
+    /// - `light`       The light source
+    ///
+    #[must_use]
+    pub fn diffuse_lighting<I: Into<Input>>(
+        self,
+        input: I,
+        color: Color,
+        surface: f64,
+        constant: f64,
+        light: Light,
+    ) -> Self {
+        let mut node = Node::new("feDiffuseLighting");
+        node.set_attribute("lighting-color", color.alpha(1.0).to_string().as_str())
+            .set_attribute("surfaceScale", decimal(surface, 6).as_str())
+            .set_attribute("diffuseConstant", decimal(constant, 6).as_str())
+            .append(light.to_node());
+        self.primitive(node, [("in", input.into())])
+    }
+
+    ///
+    /// # Light the alpha channel as a shiny surface (`feSpecularLighting`)
+    ///
+    /// - `input`       The bump map
+    /// - `color`       The color of the light
+    /// - `surface`     The height of the surface for an opaque pixel
+    /// - `constant`    The specular reflection constant
+    /// - `exponent`    The shininess, between 1 and 128
+    /// - `light`       The light source
+    ///
+    #[must_use]
+    pub fn specular_lighting<I: Into<Input>>(
+        self,
+        input: I,
+        color: Color,
+        surface: f64,
+        constant: f64,
+        exponent: f64,
+        light: Light,
+    ) -> Self {
+        let mut node = Node::new("feSpecularLighting");
+        node.set_attribute("lighting-color", color.alpha(1.0).to_string().as_str())
+            .set_attribute("surfaceScale", decimal(surface, 6).as_str())
+            .set_attribute("specularConstant", decimal(constant, 6).as_str())
+            .set_attribute("specularExponent", decimal(exponent, 6).as_str())
+            .append(light.to_node());
+        self.primitive(node, [("in", input.into())])
+    }
+
+    ///
+    /// # Repeat the input region over the primitive region (`feTile`)
+    ///
+    /// - `input` The tile
+    ///
+    #[must_use]
+    pub fn tile<I: Into<Input>>(self, input: I) -> Self {
+        self.primitive(Node::new("feTile"), [("in", input.into())])
+    }
+
+    ///
+    /// # Draw an external image or an element of the document (`feImage`)
+    ///
+    /// - `href` The image uri, or `#id` for an element
+    ///
+    #[must_use]
+    pub fn image(self, href: &str) -> Self {
+        let mut node = Node::new("feImage");
+        node.set_attribute("xlink:href", href);
+        self.primitive(node, [])
+    }
+
+    ///
+    /// # Draw a blurred and shifted shadow under the input (`feDropShadow`)
+    ///
+    /// - `input`           The image
+    /// - `dx`              The horizontal shift
+    /// - `dy`              The vertical shift
+    /// - `std_deviation`   The amount of blur
+    /// - `color`           The color of the shadow
+    ///
+    #[must_use]
+    pub fn drop_shadow<I: Into<Input>>(
+        self,
+        input: I,
+        dx: f64,
+        dy: f64,
+        std_deviation: f64,
+        color: Color,
+    ) -> Self {
+        let mut node = Node::new("feDropShadow");
+        node.set_attribute("dx", decimal(dx, 6).as_str())
+            .set_attribute("dy", decimal(dy, 6).as_str())
+            .set_attribute("stdDeviation", decimal(std_deviation, 6).as_str());
+        paint(&mut node, "flood", color);
+        self.primitive(node, [("in", input.into())])
+    }
+
+    ///
+    /// # Append a primitive reading the inputs, written before its attributes
+    ///
+    fn primitive<const N: usize>(mut self, node: Node, inputs: [(&str, Input); N]) -> Self {
+        let mut primitive = Node::new(node.tag());
+        for (name, input) in inputs {
+            self.check(&input);
+            if input != Input::Previous {
+                primitive.set_attribute(name, input.to_string().as_str());
+            }
+        }
+        for (name, value) in node.attributes() {
+            primitive.set_attribute(name, value);
+        }
+        primitive
+            .children_mut()
+            .extend(node.children().iter().cloned());
+        self.primitives.push(primitive);
+        self
+    }
+
+    ///
+    /// # Report a result read before being named
+    ///
+    fn check(&mut self, input: &Input) {
+        if let Input::Result(name) = input {
+            if !self.results.contains(name) {
+                self.fail(format!("unknown result {name}"));
+            }
+        }
+    }
+
+    fn fail(&mut self, message: String) {
+        self.error = self.error.take().or(Some(SvgError::InvalidFilter(message)));
+    }
+
+    fn attr(&mut self, name: &str, value: String) {
+        match self.attributes.iter_mut().find(|(n, _)| n == name) {
+            Some(attribute) => attribute.1 = value,
+            None => self.attributes.push((name.to_string(), value)),
+        }
+    }
+}
+
+impl Definition for Filter {
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn to_node(&self) -> Node {
+        let mut node = Node::new("filter");
+        node.set_attribute("id", self.id.as_str());
+        for (name, value) in &self.attributes {
+            node.set_attribute(name, value);
+        }
+        for primitive in &self.primitives {
+            node.append(primitive.clone());
+        }
+        node
+    }
+
+    fn take_error(&mut self) -> Option<SvgError> {
+        self.error.take()
+    }
+}
+
+///
+/// # Write a color and its opacity, `prefix-color` and `prefix-opacity`
+///
+fn paint(node: &mut Node, prefix: &str, color: Color) {
+    node.set_attribute(
+        format!("{prefix}-color").as_str(),
+        color.alpha(1.0).to_string().as_str(),
+    );
+    if color.a < 1.0 {
+        node.set_attribute(
+            format!("{prefix}-opacity").as_str(),
+            decimal(color.a.clamp(0.0, 1.0), 6).as_str(),
+        );
+    }
+}
+
+fn list(values: &[f64]) -> String {
+    values
+        .iter()
+        .map(|v| decimal(*v, 6))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+///
+/// # The value of a `filter` attribute
+///
+/// A keyword or a list of filter functions is written as is, an id becomes a
+/// reference to the filter element.
+///
+/// - `filter` The id of a filter, `none`, `inherit`, or a filter value
+///
+pub(crate) fn attribute(filter: &str) -> String {
+    if filter.contains('(') || matches!(filter.trim(), "none" | "inherit") {
+        filter.to_string()
+    } else {
+        format!("url(#{})", filter.trim_start_matches('#'))
+    }
+}
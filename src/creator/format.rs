@@ -0,0 +1,181 @@
+///
+/// # The serialization modes
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    ///
+    /// # One element per line
+    ///
+    /// - `indent` The number of spaces by level
+    ///
+    Pretty { indent: usize },
+    ///
+    /// # The whole document on one line after the xml declaration
+    ///
+    #[default]
+    Compact,
+    ///
+    /// # The smallest output
+    ///
+    /// Without xml declaration, with the shortest numbers and without
+    /// redundant separators in the geometry attributes.
+    ///
+    Minified,
+}
+
+///
+/// # The attributes made of numbers shortened by the minification
+///
+const NUMERIC: [&str; 24] = [
+    "d",
+    "points",
+    "transform",
+    "viewBox",
+    "x",
+    "y",
+    "width",
+    "height",
+    "cx",
+    "cy",
+    "r",
+    "rx",
+    "ry",
+    "x1",
+    "y1",
+    "x2",
+    "y2",
+    "dx",
+    "dy",
+    "stroke-width",
+    "stroke-miterlimit",
+    "font-size",
+    "opacity",
+    "textLength",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Number,
+    Letter,
+    Other,
+}
+
+///
+/// # Shorten an attribute value
+///
+/// - `name`    The attribute name
+/// - `value`   The attribute value
+///
+pub(crate) fn minify(name: &str, value: &str) -> String {
+    if !NUMERIC.contains(&name) {
+        return value.to_string();
+    }
+    let mut out = String::with_capacity(value.len());
+    let mut previous: Option<(Token, char)> = None;
+    let mut separated = false;
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() || c == ',' {
+            separated = true;
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let len = number_len(rest);
+        let (token, text) = if len > 0 {
+            (Token::Number, shorten(&rest[..len]))
+        } else if c.is_ascii_alphabetic() {
+            (Token::Letter, c.to_string())
+        } else {
+            (Token::Other, c.to_string())
+        };
+        if separated {
+            let space = match previous {
+                Some((Token::Number, _)) => token == Token::Number && !text.starts_with('-'),
+                Some((Token::Other, ')')) => token == Token::Letter,
+                _ => false,
+            };
+            if space {
+                out.push(' ');
+            }
+        }
+        out.push_str(text.as_str());
+        previous = Some((token, text.chars().last().unwrap_or(c)));
+        separated = false;
+        rest = &rest[if len > 0 { len } else { c.len_utf8() }..];
+    }
+    out
+}
+
+///
+/// # The length of the number at the start of `data`
+///
+pub(crate) fn number_len(data: &str) -> usize {
+    let bytes = data.as_bytes();
+    let mut i = 0;
+    if matches!(bytes.first(), Some(b'-' | b'+')) {
+        i += 1;
+    }
+    let digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+    let integer = digits(i);
+    i += integer;
+    let mut fraction = 0;
+    if bytes.get(i) == Some(&b'.') {
+        fraction = digits(i + 1);
+        if fraction > 0 || integer > 0 {
+            i += 1 + fraction;
+        }
+    }
+    if integer == 0 && fraction == 0 {
+        return 0;
+    }
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        let mut j = i + 1;
+        if matches!(bytes.get(j), Some(b'-' | b'+')) {
+            j += 1;
+        }
+        let exponent = digits(j);
+        if exponent > 0 {
+            i = j + exponent;
+        }
+    }
+    i
+}
+
+///
+/// # The shortest writing of a number
+///
+fn shorten(number: &str) -> String {
+    if number.contains(['e', 'E']) {
+        return number.to_string();
+    }
+    let Ok(value) = number.parse::<f64>() else {
+        return number.to_string();
+    };
+    let value = if value == 0.0 { 0.0 } else { value };
+    let text = value.to_string();
+    if let Some(fraction) = text.strip_prefix("0.") {
+        return format!(".{fraction}");
+    }
+    if let Some(fraction) = text.strip_prefix("-0.") {
+        return format!("-.{fraction}");
+    }
+    text
+}
+
+///
+/// # Write a number rounded to a number of decimals
+///
+/// - `value`       The number
+/// - `precision`   The maximum number of decimals
+///
+pub(crate) fn decimal(value: f64, precision: usize) -> String {
+    let scale = 10_f64.powi(i32::try_from(precision).unwrap_or(i32::MAX));
+    let rounded = (value * scale).round() / scale;
+    let rounded = if rounded.is_finite() { rounded } else { value };
+    shorten(rounded.to_string().as_str())
+}
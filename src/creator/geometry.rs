@@ -0,0 +1,623 @@
+use crate::creator::format::{decimal, number_len};
+use crate::creator::length::Length;
+use crate::creator::node::{Child, Node};
+use crate::creator::path::PathBuilder;
+use crate::creator::transform::{Matrix, Transform};
+
+///
+/// # The number of decimals written in the flattened coordinates
+///
+const PRECISION: usize = 6;
+
+///
+/// # The containers drawn by an element
+///
+const CONTAINERS: [&str; 2] = ["g", "a"];
+
+///
+/// # The attributes depending on the coordinate system of a container
+///
+const ISOLATED: [&str; 3] = ["clip-path", "mask", "filter"];
+
+///
+/// # The attributes describing the geometry of the basic shapes
+///
+const GEOMETRY: [&str; 15] = [
+    "x", "y", "width", "height", "rx", "ry", "cx", "cy", "r", "x1", "y1", "x2", "y2", "points", "d",
+];
+
+///
+/// # Read a length attribute in user units
+///
+/// None when the attribute is missing or relative
+///
+/// - `node`    The element
+/// - `name`    The attribute name
+///
+pub(crate) fn length(node: &Node, name: &str) -> Option<f64> {
+    node.attribute(name)?.parse::<Length>().ok()?.to_px()
+}
+
+///
+/// # Read a length attribute defaulting to zero
+///
+/// None when the attribute is relative
+///
+fn length_or_zero(node: &Node, name: &str) -> Option<f64> {
+    node.attribute(name)
+        .map_or(Some(0.0), |_| length(node, name))
+}
+
+///
+/// # Parse the `points` attribute of a polyline or a polygon
+///
+/// - `data` The attribute value
+///
+pub(crate) fn points(data: &str) -> Option<Vec<(f64, f64)>> {
+    let mut values = Vec::new();
+    let mut rest = data;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+        let len = number_len(rest);
+        if len == 0 {
+            return None;
+        }
+        values.push(rest[..len].parse::<f64>().ok()?);
+        rest = &rest[len..];
+    }
+    if values.len() % 2 != 0 {
+        return None;
+    }
+    Some(values.chunks(2).map(|p| (p[0], p[1])).collect())
+}
+
+///
+/// # The outline of a shape as a path
+///
+/// None when the element is not a shape or when its geometry uses relative
+/// units
+///
+/// - `node` The element
+///
+pub(crate) fn outline(node: &Node) -> Option<PathBuilder> {
+    let path = PathBuilder::new();
+    match node.tag() {
+        "rect" => {
+            let (x, y) = (length_or_zero(node, "x")?, length_or_zero(node, "y")?);
+            let (w, h) = (length(node, "width")?, length(node, "height")?);
+            let rx = node.attribute("rx").map(|_| length(node, "rx"));
+            let ry = node.attribute("ry").map(|_| length(node, "ry"));
+            let (rx, ry) = match (rx, ry) {
+                (Some(rx), Some(ry)) => (rx?, ry?),
+                (Some(r), None) | (None, Some(r)) => (r?, r?),
+                (None, None) => (0.0, 0.0),
+            };
+            Some(path.rounded_rect((x, y), (w, h), (rx, ry)))
+        }
+        "circle" => {
+            let r = length(node, "r")?;
+            Some(ellipse(path, node, (r, r))?)
+        }
+        "ellipse" => {
+            let (rx, ry) = (length(node, "rx")?, length(node, "ry")?);
+            Some(ellipse(path, node, (rx, ry))?)
+        }
+        "line" => Some(
+            path.move_to(length_or_zero(node, "x1")?, length_or_zero(node, "y1")?)
+                .line_to(length_or_zero(node, "x2")?, length_or_zero(node, "y2")?),
+        ),
+        "polyline" | "polygon" => {
+            let vertices = points(node.attribute("points")?)?;
+            let (first, rest) = vertices.split_first()?;
+            let path = rest
+                .iter()
+                .fold(path.move_to(first.0, first.1), |p, v| p.line_to(v.0, v.1));
+            Some(if node.tag() == "polygon" {
+                path.close()
+            } else {
+                path
+            })
+        }
+        "path" => node.attribute("d")?.parse().ok(),
+        _ => None,
+    }
+}
+
+fn ellipse(path: PathBuilder, node: &Node, radius: (f64, f64)) -> Option<PathBuilder> {
+    let (cx, cy) = (length_or_zero(node, "cx")?, length_or_zero(node, "cy")?);
+    Some(
+        path.move_to(cx - radius.0, cy)
+            .arc_to(radius, 0.0, true, false, (cx + radius.0, cy))
+            .arc_to(radius, 0.0, true, false, (cx - radius.0, cy))
+            .close(),
+    )
+}
+
+///
+/// # Apply the transformations of an element and its descendants to their geometry
+///
+/// The `transform` attributes are removed where the geometry can be
+/// rewritten, otherwise the accumulated transformation is kept as a matrix.
+/// The elements referenced by a `use` and their ancestors keep their own
+/// coordinates, the stroke width of a flattened container is moved to its
+/// children to be scaled with them.
+///
+/// - `node`        The element
+/// - `parent`      The transformation inherited from the ancestors
+/// - `referenced`  The ids of the elements referenced by a `use`
+/// - `width`       The stroke width inherited from the ancestors, 1 at the root
+///
+pub(crate) fn flatten(node: &mut Node, parent: Matrix, referenced: &[String], width: f64) {
+    if referenced.iter().any(|id| node.find(id).is_some()) {
+        keep(node, parent);
+        return;
+    }
+    let own = match node.attribute("transform") {
+        None => Matrix::IDENTITY,
+        Some(t) => match t.parse::<Transform>() {
+            Ok(t) => t.to_matrix(),
+            Err(_) => {
+                keep(node, parent);
+                return;
+            }
+        },
+    };
+    let matrix = parent.multiply(own);
+    node.remove_attribute("transform");
+    if CONTAINERS.contains(&node.tag()) && !ISOLATED.iter().any(|a| node.attribute(a).is_some()) {
+        let inherited = length(node, "stroke-width").unwrap_or(width);
+        let moved = if matrix.is_identity() {
+            None
+        } else {
+            node.remove_attribute("stroke-width")
+        };
+        for child in node.elements_mut() {
+            if let Some(moved) = moved.as_deref() {
+                if child.attribute("stroke-width").is_none() {
+                    child.set_attribute("stroke-width", moved);
+                }
+            }
+            flatten(child, matrix, referenced, inherited);
+        }
+        return;
+    }
+    if !matrix.is_identity() && !apply(node, matrix, width) {
+        node.set_attribute("transform", matrix.to_string().as_str());
+    }
+}
+
+///
+/// # Prepend the inherited transformation to the `transform` attribute
+///
+/// - `node`    The element kept in its own coordinates
+/// - `parent`  The transformation inherited from the ancestors
+///
+fn keep(node: &mut Node, parent: Matrix) {
+    if parent.is_identity() {
+        return;
+    }
+    let kept = match node.attribute("transform") {
+        Some(t) => format!("{parent} {t}"),
+        None => parent.to_string(),
+    };
+    node.set_attribute("transform", kept.as_str());
+}
+
+///
+/// # The ids of the elements referenced by the `use` elements
+///
+/// - `node` The root of the search
+///
+pub(crate) fn referenced(node: &Node) -> Vec<String> {
+    let mut ids = Vec::new();
+    if node.tag() == "use" {
+        if let Some(href) = node
+            .attribute("href")
+            .or_else(|| node.attribute("xlink:href"))
+        {
+            ids.push(href.trim_start_matches('#').to_string());
+        }
+    }
+    for child in node.elements() {
+        ids.extend(referenced(child));
+    }
+    ids
+}
+
+///
+/// # Rewrite the geometry of a shape
+///
+/// The stroke width is scaled with the shape, the inherited width is written
+/// on the shape when it does not define one.
+///
+/// - `node`    The shape
+/// - `m`       The transformation
+/// - `width`   The stroke width inherited from the ancestors
+///
+/// # Return
+///
+/// false when the element can not be rewritten
+///
+fn apply(node: &mut Node, m: Matrix, width: f64) -> bool {
+    if ISOLATED.iter().any(|a| node.attribute(a).is_some()) {
+        return false;
+    }
+    let n = |v: f64| decimal(v, PRECISION);
+    let axis_aligned = m.b.abs() < 1e-12 && m.c.abs() < 1e-12;
+    let conformal =
+        (m.a.hypot(m.b) - m.c.hypot(m.d)).abs() < 1e-9 && m.a.mul_add(m.c, m.b * m.d).abs() < 1e-9;
+    let done = match node.tag() {
+        "line" => {
+            let (Some(x1), Some(y1), Some(x2), Some(y2)) = (
+                length_or_zero(node, "x1"),
+                length_or_zero(node, "y1"),
+                length_or_zero(node, "x2"),
+                length_or_zero(node, "y2"),
+            ) else {
+                return false;
+            };
+            let (x1, y1) = m.apply((x1, y1));
+            let (x2, y2) = m.apply((x2, y2));
+            node.set_attribute("x1", n(x1).as_str())
+                .set_attribute("y1", n(y1).as_str())
+                .set_attribute("x2", n(x2).as_str())
+                .set_attribute("y2", n(y2).as_str());
+            true
+        }
+        "polyline" | "polygon" => {
+            let Some(vertices) = node.attribute("points").and_then(points) else {
+                return false;
+            };
+            let written: Vec<String> = vertices
+                .into_iter()
+                .map(|p| {
+                    let (x, y) = m.apply(p);
+                    format!("{},{}", n(x), n(y))
+                })
+                .collect();
+            node.set_attribute("points", written.join(" ").as_str());
+            true
+        }
+        "rect" if axis_aligned => {
+            let (Some(x), Some(y), Some(w), Some(h)) = (
+                length_or_zero(node, "x"),
+                length_or_zero(node, "y"),
+                length(node, "width"),
+                length(node, "height"),
+            ) else {
+                return false;
+            };
+            let rx = node.attribute("rx").map(|_| length(node, "rx"));
+            let ry = node.attribute("ry").map(|_| length(node, "ry"));
+            let radius = match (rx, ry) {
+                (None, None) => None,
+                (Some(Some(rx)), Some(Some(ry))) => Some((rx, ry)),
+                (Some(Some(r)), None) | (None, Some(Some(r))) => Some((r, r)),
+                _ => return false,
+            };
+            let (x1, y1) = m.apply((x, y));
+            let (x2, y2) = m.apply((x + w, y + h));
+            node.set_attribute("x", n(x1.min(x2)).as_str())
+                .set_attribute("y", n(y1.min(y2)).as_str())
+                .set_attribute("width", n((x2 - x1).abs()).as_str())
+                .set_attribute("height", n((y2 - y1).abs()).as_str());
+            if let Some((rx, ry)) = radius {
+                node.set_attribute("rx", n(rx * m.a.abs()).as_str())
+                    .set_attribute("ry", n(ry * m.d.abs()).as_str());
+            }
+            true
+        }
+        "circle" if conformal => {
+            let (Some(cx), Some(cy), Some(r)) = (
+                length_or_zero(node, "cx"),
+                length_or_zero(node, "cy"),
+                length(node, "r"),
+            ) else {
+                return false;
+            };
+            let (cx, cy) = m.apply((cx, cy));
+            node.set_attribute("cx", n(cx).as_str())
+                .set_attribute("cy", n(cy).as_str())
+                .set_attribute("r", n(r * m.a.hypot(m.b)).as_str());
+            true
+        }
+        "ellipse" if axis_aligned => {
+            let (Some(cx), Some(cy), Some(rx), Some(ry)) = (
+                length_or_zero(node, "cx"),
+                length_or_zero(node, "cy"),
+                length(node, "rx"),
+                length(node, "ry"),
+            ) else {
+                return false;
+            };
+            let (cx, cy) = m.apply((cx, cy));
+            node.set_attribute("cx", n(cx).as_str())
+                .set_attribute("cy", n(cy).as_str())
+                .set_attribute("rx", n(rx * m.a.abs()).as_str())
+                .set_attribute("ry", n(ry * m.d.abs()).as_str());
+            true
+        }
+        "rect" | "circle" | "ellipse" | "path" => {
+            let Some(path) = outline(node) else {
+                return false;
+            };
+            let mut converted = Node::new("path");
+            for (name, value) in node.attributes() {
+                if !GEOMETRY.contains(&name) {
+                    converted.set_attribute(name, value);
+                }
+            }
+            converted.set_attribute("d", path.transform(m).to_string().as_str());
+            let children: Vec<Child> = node.children_mut().drain(..).collect();
+            converted.children_mut().extend(children);
+            *node = converted;
+            true
+        }
+        _ => false,
+    };
+    if done {
+        let scale = m.a.mul_add(m.d, -m.b * m.c).abs().sqrt();
+        let width = match node.attribute("stroke-width") {
+            Some(_) => length(node, "stroke-width"),
+            None if (scale - 1.0).abs() > 1e-12 => Some(width),
+            None => None,
+        };
+        if let Some(width) = width {
+            node.set_attribute("stroke-width", n(width * scale).as_str());
+        }
+    }
+    done
+}
+
+///
+/// # An axis aligned rectangle enclosing a geometry
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl BoundingBox {
+    ///
+    /// # Constructor
+    ///
+    /// The corners are sorted.
+    ///
+    #[must_use]
+    pub fn new(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Self {
+        Self {
+            min_x: min_x.min(max_x),
+            min_y: min_y.min(max_y),
+            max_x: min_x.max(max_x),
+            max_y: min_y.max(max_y),
+        }
+    }
+
+    ///
+    /// # The box of a single point
+    ///
+    #[must_use]
+    pub const fn point(point: (f64, f64)) -> Self {
+        Self {
+            min_x: point.0,
+            min_y: point.1,
+            max_x: point.0,
+            max_y: point.1,
+        }
+    }
+
+    ///
+    /// # The width
+    ///
+    #[must_use]
+    pub fn width(self) -> f64 {
+        self.max_x - self.min_x
+    }
+
+    ///
+    /// # The height
+    ///
+    #[must_use]
+    pub fn height(self) -> f64 {
+        self.max_y - self.min_y
+    }
+
+    ///
+    /// # Extend the box to a point
+    ///
+    #[must_use]
+    pub fn include(self, point: (f64, f64)) -> Self {
+        self.union(Self::point(point))
+    }
+
+    ///
+    /// # The box enclosing two boxes
+    ///
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+
+    ///
+    /// # Grow the box on each side
+    ///
+    /// - `padding` The distance added on each side
+    ///
+    #[must_use]
+    pub fn pad(self, padding: f64) -> Self {
+        Self::new(
+            self.min_x - padding,
+            self.min_y - padding,
+            self.max_x + padding,
+            self.max_y + padding,
+        )
+    }
+}
+
+///
+/// # The elements never rendered directly
+///
+const HIDDEN: [&str; 12] = [
+    "defs",
+    "title",
+    "desc",
+    "metadata",
+    "style",
+    "clipPath",
+    "mask",
+    "pattern",
+    "marker",
+    "symbol",
+    "linearGradient",
+    "radialGradient",
+];
+
+///
+/// # The maximum number of nested `use` resolved
+///
+const MAX_DEPTH: usize = 16;
+
+///
+/// # The stroke inherited from the ancestors
+///
+#[derive(Clone, Copy)]
+struct Stroke {
+    painted: bool,
+    width: f64,
+}
+
+impl Stroke {
+    fn inherit(self, node: &Node) -> Self {
+        Self {
+            painted: node
+                .attribute("stroke")
+                .map_or(self.painted, |s| s != "none"),
+            width: length(node, "stroke-width").unwrap_or(self.width),
+        }
+    }
+}
+
+///
+/// # The bounding box of an element and its descendants
+///
+/// The texts are ignored, the `use` elements are resolved in `root`.
+///
+/// - `root`    The document root
+/// - `node`    The element
+/// - `matrix`  The transformation of the coordinates of the element
+/// - `stroke`  Include the half of the stroke width
+///
+pub(crate) fn bounding_box(
+    root: &Node,
+    node: &Node,
+    matrix: Matrix,
+    stroke: bool,
+) -> Option<BoundingBox> {
+    let painted = Stroke {
+        painted: false,
+        width: 1.0,
+    };
+    measure(root, node, matrix, stroke.then_some(painted), 0)
+}
+
+///
+/// # Find an element by id with the transformation of its ancestors
+///
+/// - `node`    The root of the search
+/// - `id`      The id of the element
+///
+pub(crate) fn locate<'a>(node: &'a Node, id: &str, parent: Matrix) -> Option<(&'a Node, Matrix)> {
+    let matrix = parent.multiply(own_matrix(node));
+    if node.attribute("id") == Some(id) {
+        return Some((node, parent));
+    }
+    node.elements().find_map(|child| locate(child, id, matrix))
+}
+
+fn own_matrix(node: &Node) -> Matrix {
+    node.attribute("transform")
+        .and_then(|t| t.parse::<Transform>().ok())
+        .map_or(Matrix::IDENTITY, |t| t.to_matrix())
+}
+
+fn measure(
+    root: &Node,
+    node: &Node,
+    parent: Matrix,
+    stroke: Option<Stroke>,
+    depth: usize,
+) -> Option<BoundingBox> {
+    if HIDDEN.contains(&node.tag()) || depth > MAX_DEPTH {
+        return None;
+    }
+    let matrix = parent.multiply(own_matrix(node));
+    let stroke = stroke.map(|s| s.inherit(node));
+    let shape = match node.tag() {
+        "circle" | "ellipse" => ellipse_box(node, matrix),
+        "image" => {
+            let (x, y) = (length_or_zero(node, "x")?, length_or_zero(node, "y")?);
+            let (w, h) = (length(node, "width")?, length(node, "height")?);
+            [(x, y), (x + w, y), (x, y + h), (x + w, y + h)]
+                .into_iter()
+                .map(|p| BoundingBox::point(matrix.apply(p)))
+                .reduce(BoundingBox::union)
+        }
+        "use" => {
+            let href = node
+                .attribute("href")
+                .or_else(|| node.attribute("xlink:href"))?;
+            let (target, _) = locate(root, href.trim_start_matches('#'), Matrix::IDENTITY)?;
+            let offset = Matrix::new(
+                1.0,
+                0.0,
+                0.0,
+                1.0,
+                length_or_zero(node, "x")?,
+                length_or_zero(node, "y")?,
+            );
+            return measure(root, target, matrix.multiply(offset), stroke, depth + 1);
+        }
+        _ => outline(node).and_then(|path| path.transform(matrix).bounding_box()),
+    };
+    let shape = match (shape, stroke) {
+        (Some(b), Some(s)) if s.painted => {
+            let scale = matrix
+                .a
+                .mul_add(matrix.d, -matrix.b * matrix.c)
+                .abs()
+                .sqrt();
+            Some(b.pad(s.width * scale / 2.0))
+        }
+        (shape, _) => shape,
+    };
+    node.elements()
+        .filter_map(|child| measure(root, child, matrix, stroke, depth))
+        .fold(shape, |acc, b| Some(acc.map_or(b, |a| a.union(b))))
+}
+
+///
+/// # The exact box of a transformed circle or ellipse
+///
+fn ellipse_box(node: &Node, m: Matrix) -> Option<BoundingBox> {
+    let (rx, ry) = if node.tag() == "circle" {
+        let r = length(node, "r")?;
+        (r, r)
+    } else {
+        (length(node, "rx")?, length(node, "ry")?)
+    };
+    let (cx, cy) = m.apply((length_or_zero(node, "cx")?, length_or_zero(node, "cy")?));
+    let hx = (m.a * rx).hypot(m.c * ry);
+    let hy = (m.b * rx).hypot(m.d * ry);
+    Some(BoundingBox::new(cx - hx, cy - hy, cx + hx, cy + hy))
+}
@@ -0,0 +1,365 @@
+use crate::creator::color::{Color, Paint};
+use crate::creator::definition::{Definition, Units};
+use crate::creator::error::SvgError;
+use crate::creator::format::decimal;
+use crate::creator::length::Length;
+use crate::creator::node::Node;
+use crate::creator::transform::Transform;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+
+///
+/// # The kind of a gradient
+///
+pub trait Shading {
+    const TAG: &'static str;
+}
+
+///
+/// # A gradient along a line
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Linear;
+
+///
+/// # A gradient around a center
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Radial;
+
+impl Shading for Linear {
+    const TAG: &'static str = "linearGradient";
+}
+
+impl Shading for Radial {
+    const TAG: &'static str = "radialGradient";
+}
+
+pub type LinearGradient = Gradient<Linear>;
+pub type RadialGradient = Gradient<Radial>;
+
+///
+/// # How the gradient fills the area outside of its bounds
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpreadMethod {
+    ///
+    /// # Extend the colors of the ends
+    ///
+    #[default]
+    Pad,
+    ///
+    /// # Repeat the gradient in alternate directions
+    ///
+    Reflect,
+    ///
+    /// # Repeat the gradient from the start
+    ///
+    Repeat,
+}
+
+impl SpreadMethod {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pad => "pad",
+            Self::Reflect => "reflect",
+            Self::Repeat => "repeat",
+        }
+    }
+}
+
+impl Display for SpreadMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+///
+/// # A color of a gradient
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stop {
+    ///
+    /// # The position between 0 and 1
+    ///
+    pub offset: f64,
+    ///
+    /// # A color, `CurrentColor` or `Inherit`
+    ///
+    /// `None` is a transparent stop, a `Url` is reported by `Svg::define`.
+    ///
+    pub color: Paint,
+    ///
+    /// # The opacity between 0 and 1, multiplied by the alpha of the color
+    ///
+    pub opacity: f64,
+}
+
+impl Stop {
+    ///
+    /// # Constructor
+    ///
+    /// - `offset`  The position between 0 and 1
+    /// - `color`   The color, `Paint::CurrentColor` or `Paint::Inherit`
+    ///
+    #[must_use]
+    pub fn new<P: Into<Paint>>(offset: f64, color: P) -> Self {
+        Self {
+            offset,
+            color: color.into(),
+            opacity: 1.0,
+        }
+    }
+
+    ///
+    /// # Define the opacity
+    ///
+    /// - `opacity` The opacity between 0 and 1
+    ///
+    #[must_use]
+    pub const fn opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    fn to_node(&self) -> Node {
+        let mut stop = Node::new("stop");
+        stop.set_attribute("offset", decimal(self.offset.clamp(0.0, 1.0), 6).as_str());
+        let alpha = match &self.color {
+            Paint::None => {
+                stop.set_attribute("stop-color", "#000000");
+                0.0
+            }
+            color => {
+                stop.set_attribute("stop-color", color.to_string().as_str());
+                color.opacity().unwrap_or(1.0)
+            }
+        };
+        let opacity = (alpha * self.opacity).clamp(0.0, 1.0);
+        if opacity < 1.0 {
+            stop.set_attribute("stop-opacity", decimal(opacity, 6).as_str());
+        }
+        stop
+    }
+}
+
+impl<P: Into<Paint>> From<(f64, P)> for Stop {
+    fn from((offset, color): (f64, P)) -> Self {
+        Self::new(offset, color)
+    }
+}
+
+///
+/// # A gradient definition
+///
+/// The gradient is registered with `Svg::define` which returns the id used by
+/// `Paint::Url`.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient<K: Shading> {
+    id: String,
+    attributes: Vec<(String, String)>,
+    href: Option<String>,
+    stops: Vec<Stop>,
+    kind: PhantomData<K>,
+}
+
+impl<K: Shading> Gradient<K> {
+    ///
+    /// # Constructor
+    ///
+    /// - `id` The id of the gradient
+    ///
+    #[must_use]
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            attributes: Vec::new(),
+            href: None,
+            stops: Vec::new(),
+            kind: PhantomData,
+        }
+    }
+
+    ///
+    /// # Add a stop
+    ///
+    /// - `stop` The stop, or an offset and a color
+    ///
+    #[must_use]
+    pub fn stop<S: Into<Stop>>(mut self, stop: S) -> Self {
+        self.stops.push(stop.into());
+        self
+    }
+
+    ///
+    /// # The stops
+    ///
+    #[must_use]
+    pub fn stops(&self) -> &[Stop] {
+        &self.stops
+    }
+
+    ///
+    /// # Define the coordinate system of the geometry
+    ///
+    /// - `units` The units, `ObjectBoundingBox` when not defined
+    ///
+    #[must_use]
+    pub fn units(self, units: Units) -> Self {
+        self.attr("gradientUnits", units.to_string())
+    }
+
+    ///
+    /// # Transform the gradient
+    ///
+    /// - `transform` The transformation
+    ///
+    #[must_use]
+    pub fn transform(self, transform: &Transform) -> Self {
+        self.attr("gradientTransform", transform.to_string())
+    }
+
+    ///
+    /// # Define how the area outside of the gradient is filled
+    ///
+    /// - `spread` The spread method
+    ///
+    #[must_use]
+    pub fn spread(self, spread: SpreadMethod) -> Self {
+        self.attr("spreadMethod", spread.to_string())
+    }
+
+    ///
+    /// # Inherit the attributes and the stops of another gradient
+    ///
+    /// The attributes and the stops defined on this gradient take precedence.
+    ///
+    /// - `id` The id of the inherited gradient
+    ///
+    #[must_use]
+    pub fn href(mut self, id: &str) -> Self {
+        self.href = Some(id.trim_start_matches('#').to_string());
+        self
+    }
+
+    fn attr(mut self, name: &str, value: String) -> Self {
+        match self.attributes.iter_mut().find(|(n, _)| n == name) {
+            Some(attribute) => attribute.1 = value,
+            None => self.attributes.push((name.to_string(), value)),
+        }
+        self
+    }
+
+    fn length<L: Into<Length>>(self, name: &str, value: L) -> Self {
+        self.attr(name, value.into().to_string())
+    }
+}
+
+impl Gradient<Linear> {
+    ///
+    /// # Define the start point
+    ///
+    #[must_use]
+    pub fn start<X: Into<Length>, Y: Into<Length>>(self, x1: X, y1: Y) -> Self {
+        self.length("x1", x1).length("y1", y1)
+    }
+
+    ///
+    /// # Define the end point
+    ///
+    #[must_use]
+    pub fn end<X: Into<Length>, Y: Into<Length>>(self, x2: X, y2: Y) -> Self {
+        self.length("x2", x2).length("y2", y2)
+    }
+
+    ///
+    /// # The gloss of the shields.io plastic badges
+    ///
+    /// A vertical gradient from a light top to a dark bottom, drawn over the
+    /// badge background.
+    ///
+    /// - `id` The id of the gradient
+    ///
+    #[must_use]
+    pub fn plastic(id: &str) -> Self {
+        Self::new(id)
+            .end(0.0, Length::percent(100.0))
+            .stop(Stop::new(0.0, Color::WHITE).opacity(0.7))
+            .stop(Stop::new(0.1, Color::hex(0x00aa_aaaa)).opacity(0.1))
+            .stop(Stop::new(0.9, Color::BLACK).opacity(0.3))
+            .stop(Stop::new(1.0, Color::BLACK).opacity(0.5))
+    }
+}
+
+impl Gradient<Radial> {
+    ///
+    /// # Define the end circle
+    ///
+    /// - `cx`  The x-coordinate of the center
+    /// - `cy`  The y-coordinate of the center
+    /// - `r`   The radius
+    ///
+    #[must_use]
+    pub fn circle<X, Y, R>(self, cx: X, cy: Y, r: R) -> Self
+    where
+        X: Into<Length>,
+        Y: Into<Length>,
+        R: Into<Length>,
+    {
+        self.length("cx", cx).length("cy", cy).length("r", r)
+    }
+
+    ///
+    /// # Define the start circle
+    ///
+    /// - `fx`  The x-coordinate of the focal point
+    /// - `fy`  The y-coordinate of the focal point
+    /// - `fr`  The radius of the focal circle
+    ///
+    #[must_use]
+    pub fn focus<X, Y, R>(self, fx: X, fy: Y, fr: R) -> Self
+    where
+        X: Into<Length>,
+        Y: Into<Length>,
+        R: Into<Length>,
+    {
+        self.length("fx", fx).length("fy", fy).length("fr", fr)
+    }
+}
+
+impl<K: Shading> Definition for Gradient<K> {
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn to_node(&self) -> Node {
+        let mut node = Node::new(K::TAG);
+        node.set_attribute("id", self.id.as_str());
+        for (name, value) in &self.attributes {
+            node.set_attribute(name, value);
+        }
+        if let Some(href) = &self.href {
+            node.set_attribute("xlink:href", format!("#{href}").as_str());
+        }
+        for stop in &self.stops {
+            node.append(stop.to_node());
+        }
+        node
+    }
+
+    fn inherits(&self) -> Option<&str> {
+        self.href.as_deref()
+    }
+
+    fn take_error(&mut self) -> Option<SvgError> {
+        self.stops.iter().find_map(|stop| match &stop.color {
+            Paint::Url(id) => Some(SvgError::InvalidColor(format!("url(#{id})"))),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt::{Display, Formatter};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+///
+/// # The image formats accepted by the image element
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MimeType {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Svg,
+}
+
+impl MimeType {
+    ///
+    /// # Detect the format from the magic bytes
+    ///
+    /// - `data` The image content
+    ///
+    #[must_use]
+    pub fn sniff(data: &[u8]) -> Option<Self> {
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            return Some(Self::Png);
+        }
+        if data.starts_with(b"\xff\xd8\xff") {
+            return Some(Self::Jpeg);
+        }
+        if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            return Some(Self::Gif);
+        }
+        if data.len() >= 12 && data.starts_with(b"RIFF") && &data[8..12] == b"WEBP" {
+            return Some(Self::Webp);
+        }
+        let head = String::from_utf8_lossy(&data[..data.len().min(1024)]);
+        let head = head.trim_start_matches('\u{feff}').trim_start();
+        if head.starts_with("<svg") || (head.starts_with('<') && head.contains("<svg")) {
+            return Some(Self::Svg);
+        }
+        None
+    }
+
+    ///
+    /// # The mime type name
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Gif => "image/gif",
+            Self::Webp => "image/webp",
+            Self::Svg => "image/svg+xml",
+        }
+    }
+}
+
+impl Display for MimeType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+///
+/// # Encode data in base64
+///
+/// - `data` The data to encode
+///
+#[must_use]
+pub fn base64(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(char::from(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+///
+/// # Build a data uri
+///
+/// - `data` The image content
+///
+/// # Return
+///
+/// None when the format is not recognized
+///
+#[must_use]
+pub fn data_uri(data: &[u8]) -> Option<String> {
+    let mime = MimeType::sniff(data)?;
+    Some(format!("data:{mime};base64,{}", base64(data)))
+}
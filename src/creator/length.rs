@@ -0,0 +1,254 @@
+use crate::creator::error::SvgError;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+///
+/// # The length units
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unit {
+    Px,
+    Em,
+    Ex,
+    Percent,
+    Pt,
+    Pc,
+    Mm,
+    Cm,
+    In,
+    ///
+    /// # A length in user units
+    ///
+    #[default]
+    None,
+}
+
+impl Unit {
+    ///
+    /// # The unit suffix
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Px => "px",
+            Self::Em => "em",
+            Self::Ex => "ex",
+            Self::Percent => "%",
+            Self::Pt => "pt",
+            Self::Pc => "pc",
+            Self::Mm => "mm",
+            Self::Cm => "cm",
+            Self::In => "in",
+            Self::None => "",
+        }
+    }
+
+    ///
+    /// # The number of pixels in one unit
+    ///
+    /// None for the relative units
+    ///
+    #[must_use]
+    pub const fn pixels(self) -> Option<f64> {
+        match self {
+            Self::Px | Self::None => Some(1.0),
+            Self::Pt => Some(96.0 / 72.0),
+            Self::Pc => Some(16.0),
+            Self::Mm => Some(96.0 / 25.4),
+            Self::Cm => Some(96.0 / 2.54),
+            Self::In => Some(96.0),
+            Self::Em | Self::Ex | Self::Percent => None,
+        }
+    }
+}
+
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Unit {
+    type Err = SvgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "px" => Ok(Self::Px),
+            "em" => Ok(Self::Em),
+            "ex" => Ok(Self::Ex),
+            "%" => Ok(Self::Percent),
+            "pt" => Ok(Self::Pt),
+            "pc" => Ok(Self::Pc),
+            "mm" => Ok(Self::Mm),
+            "cm" => Ok(Self::Cm),
+            "in" => Ok(Self::In),
+            "" => Ok(Self::None),
+            _ => Err(SvgError::InvalidLength(s.to_string())),
+        }
+    }
+}
+
+///
+/// # A length with his unit
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Length {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Length {
+    ///
+    /// # Constructor
+    ///
+    /// - `value`   The value
+    /// - `unit`    The unit
+    ///
+    #[must_use]
+    pub const fn new(value: f64, unit: Unit) -> Self {
+        Self { value, unit }
+    }
+
+    ///
+    /// # A length in pixels
+    ///
+    #[must_use]
+    pub const fn px(value: f64) -> Self {
+        Self::new(value, Unit::Px)
+    }
+
+    ///
+    /// # A length relative to the font size
+    ///
+    #[must_use]
+    pub const fn em(value: f64) -> Self {
+        Self::new(value, Unit::Em)
+    }
+
+    ///
+    /// # A length relative to the x-height of the font
+    ///
+    #[must_use]
+    pub const fn ex(value: f64) -> Self {
+        Self::new(value, Unit::Ex)
+    }
+
+    ///
+    /// # A percentage
+    ///
+    #[must_use]
+    pub const fn percent(value: f64) -> Self {
+        Self::new(value, Unit::Percent)
+    }
+
+    ///
+    /// # A length in points
+    ///
+    #[must_use]
+    pub const fn pt(value: f64) -> Self {
+        Self::new(value, Unit::Pt)
+    }
+
+    ///
+    /// # A length in picas
+    ///
+    #[must_use]
+    pub const fn pc(value: f64) -> Self {
+        Self::new(value, Unit::Pc)
+    }
+
+    ///
+    /// # A length in millimeters
+    ///
+    #[must_use]
+    pub const fn mm(value: f64) -> Self {
+        Self::new(value, Unit::Mm)
+    }
+
+    ///
+    /// # A length in centimeters
+    ///
+    #[must_use]
+    pub const fn cm(value: f64) -> Self {
+        Self::new(value, Unit::Cm)
+    }
+
+    ///
+    /// # A length in inches
+    ///
+    #[must_use]
+    pub const fn inches(value: f64) -> Self {
+        Self::new(value, Unit::In)
+    }
+
+    ///
+    /// # A length in user units
+    ///
+    #[must_use]
+    pub const fn unitless(value: f64) -> Self {
+        Self::new(value, Unit::None)
+    }
+
+    ///
+    /// # Convert to another absolute unit
+    ///
+    /// - `unit` The new unit
+    ///
+    /// # Return
+    ///
+    /// None when one of the units is relative
+    ///
+    #[must_use]
+    pub fn to(self, unit: Unit) -> Option<Self> {
+        if self.unit == unit {
+            return Some(self);
+        }
+        Some(Self::new(self.to_px()? / unit.pixels()?, unit))
+    }
+
+    ///
+    /// # The length in pixels
+    ///
+    /// None for the relative units
+    ///
+    #[must_use]
+    pub fn to_px(self) -> Option<f64> {
+        Some(self.value * self.unit.pixels()?)
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+impl FromStr for Length {
+    type Err = SvgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let end = s
+            .rfind(|c: char| c.is_ascii_digit() || c == '.')
+            .map_or(0, |i| i + 1);
+        let value = s[..end]
+            .parse()
+            .map_err(|_| SvgError::InvalidLength(s.to_string()))?;
+        let unit = s[end..]
+            .parse()
+            .map_err(|_| SvgError::InvalidLength(s.to_string()))?;
+        Ok(Self::new(value, unit))
+    }
+}
+
+impl From<f64> for Length {
+    fn from(value: f64) -> Self {
+        Self::unitless(value)
+    }
+}
+
+impl From<(f64, Unit)> for Length {
+    fn from((value, unit): (f64, Unit)) -> Self {
+        Self::new(value, unit)
+    }
+}
@@ -0,0 +1,260 @@
+use crate::creator::color::{Color, Paint};
+use crate::creator::definition::{Drawing, Template};
+use crate::creator::format::decimal;
+use crate::creator::viewport::ViewBox;
+use std::fmt::{Display, Formatter};
+
+///
+/// # The rotation of a marker
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Orient {
+    ///
+    /// # Follow the direction of the path
+    ///
+    #[default]
+    Auto,
+    ///
+    /// # Follow the direction of the path, reversed at the start
+    ///
+    AutoStartReverse,
+    ///
+    /// # A fixed angle in degrees
+    ///
+    Angle(f64),
+}
+
+impl Display for Orient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::AutoStartReverse => f.write_str("auto-start-reverse"),
+            Self::Angle(angle) => f.write_str(decimal(*angle, 6).as_str()),
+        }
+    }
+}
+
+///
+/// # The coordinate system of the marker size
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkerUnits {
+    ///
+    /// # Multiples of the stroke width of the marked element
+    ///
+    #[default]
+    StrokeWidth,
+    ///
+    /// # The user space of the marked element
+    ///
+    UserSpaceOnUse,
+}
+
+impl MarkerUnits {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::StrokeWidth => "strokeWidth",
+            Self::UserSpaceOnUse => "userSpaceOnUse",
+        }
+    }
+}
+
+impl Display for MarkerUnits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+///
+/// # The kind of the marker definitions
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Marking;
+
+impl Drawing for Marking {
+    const TAG: &'static str = "marker";
+}
+
+///
+/// # A marker definition
+///
+/// The marker is drawn with `content` at the vertices of the lines,
+/// polylines, polygons and paths referencing it with `marker_start`,
+/// `marker_mid` or `marker_end`.
+///
+pub type Marker = Template<Marking>;
+
+impl Marker {
+    ///
+    /// # Constructor
+    ///
+    /// - `id` The id of the marker
+    ///
+    #[must_use]
+    pub fn new(id: &str) -> Self {
+        Self::with_id(id)
+    }
+
+    ///
+    /// # Define the point of the marker placed on the vertex
+    ///
+    /// - `x` The x-coordinate in the marker content
+    /// - `y` The y-coordinate in the marker content
+    ///
+    #[must_use]
+    pub fn reference(self, x: f64, y: f64) -> Self {
+        self.attr("refX", decimal(x, 6)).attr("refY", decimal(y, 6))
+    }
+
+    ///
+    /// # Define the size of the marker viewport
+    ///
+    /// - `width`   The width, 3 when not defined
+    /// - `height`  The height, 3 when not defined
+    ///
+    #[must_use]
+    pub fn size(self, width: f64, height: f64) -> Self {
+        self.attr("markerWidth", decimal(width, 6))
+            .attr("markerHeight", decimal(height, 6))
+    }
+
+    ///
+    /// # Define the rotation of the marker
+    ///
+    /// - `orient` The rotation, a fixed angle of 0 when not defined
+    ///
+    #[must_use]
+    pub fn orient(self, orient: Orient) -> Self {
+        self.attr("orient", orient.to_string())
+    }
+
+    ///
+    /// # Define the coordinate system of the marker size
+    ///
+    /// - `units` The units, `StrokeWidth` when not defined
+    ///
+    #[must_use]
+    pub fn units(self, units: MarkerUnits) -> Self {
+        self.attr("markerUnits", units.to_string())
+    }
+
+    ///
+    /// # Define the user space of the marker content
+    ///
+    /// - `view_box` The area of the content shown in the marker viewport
+    ///
+    #[must_use]
+    pub fn view_box(self, view_box: ViewBox) -> Self {
+        self.attr("viewBox", view_box.to_string())
+    }
+
+    ///
+    /// # A filled triangle pointing in the direction of the path
+    ///
+    /// - `id`      The id of the marker
+    /// - `color`   The color of the arrow
+    ///
+    #[must_use]
+    pub fn arrow(id: &str, color: Color) -> Self {
+        Self::stock(id, 10.0, 5.0).content(|marker| {
+            marker.path().d("M0 0L10 5L0 10z").fill(color).close();
+        })
+    }
+
+    ///
+    /// # An unfilled chevron pointing in the direction of the path
+    ///
+    /// - `id`      The id of the marker
+    /// - `color`   The color of the arrow
+    ///
+    #[must_use]
+    pub fn open_arrow(id: &str, color: Color) -> Self {
+        Self::stock(id, 9.0, 5.0).content(|marker| {
+            marker
+                .path()
+                .d("M1 1L9 5L1 9")
+                .fill(Paint::None)
+                .stroke(color)
+                .stroke_width(1.5)
+                .close();
+        })
+    }
+
+    ///
+    /// # A disc centered on the vertex
+    ///
+    /// - `id`      The id of the marker
+    /// - `color`   The color of the disc
+    ///
+    #[must_use]
+    pub fn dot(id: &str, color: Color) -> Self {
+        Self::stock(id, 5.0, 5.0).content(|marker| {
+            marker
+                .circle()
+                .cx(5.0)
+                .cy(5.0)
+                .r(5.0)
+                .fill(color)
+                .close_circle();
+        })
+    }
+
+    ///
+    /// # A square centered on the vertex
+    ///
+    /// - `id`      The id of the marker
+    /// - `color`   The color of the square
+    ///
+    #[must_use]
+    pub fn square(id: &str, color: Color) -> Self {
+        Self::stock(id, 5.0, 5.0).content(|marker| {
+            marker
+                .rect()
+                .width(10.0)
+                .height(10.0)
+                .fill(color)
+                .close_rect();
+        })
+    }
+
+    ///
+    /// # A diamond centered on the vertex
+    ///
+    /// - `id`      The id of the marker
+    /// - `color`   The color of the diamond
+    ///
+    #[must_use]
+    pub fn diamond(id: &str, color: Color) -> Self {
+        Self::stock(id, 5.0, 5.0).content(|marker| {
+            marker.path().d("M5 0L10 5L5 10L0 5z").fill(color).close();
+        })
+    }
+
+    ///
+    /// # A bar across the path
+    ///
+    /// - `id`      The id of the marker
+    /// - `color`   The color of the bar
+    ///
+    #[must_use]
+    pub fn bar(id: &str, color: Color) -> Self {
+        Self::stock(id, 5.0, 5.0).content(|marker| {
+            marker
+                .line(5.0, 0.0, 5.0, 10.0, color)
+                .stroke_width(2.0)
+                .close();
+        })
+    }
+
+    fn stock(id: &str, x: f64, y: f64) -> Self {
+        Self::new(id)
+            .view_box(ViewBox::new(0.0, 0.0, 10.0, 10.0))
+            .reference(x, y)
+            .size(6.0, 6.0)
+            .orient(Orient::AutoStartReverse)
+    }
+}
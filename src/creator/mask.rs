@@ -0,0 +1,120 @@
+use crate::creator::definition::{Drawing, Template, Units};
+use crate::creator::length::Length;
+use std::fmt::{Display, Formatter};
+
+///
+/// # The channel of the mask content defining the opacity
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MaskType {
+    ///
+    /// # The brightness of the content, white is opaque and black transparent
+    ///
+    #[default]
+    Luminance,
+    ///
+    /// # The alpha channel of the content
+    ///
+    Alpha,
+}
+
+impl MaskType {
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Luminance => "luminance",
+            Self::Alpha => "alpha",
+        }
+    }
+}
+
+impl Display for MaskType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+///
+/// # The kind of the mask definitions
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Masking;
+
+impl Drawing for Masking {
+    const TAG: &'static str = "mask";
+}
+
+///
+/// # A mask definition
+///
+/// The content drawn with `content` defines the opacity of the elements
+/// referencing the mask with `mask`.
+///
+pub type Mask = Template<Masking>;
+
+impl Mask {
+    ///
+    /// # Constructor
+    ///
+    /// - `id` The id of the mask
+    ///
+    #[must_use]
+    pub fn new(id: &str) -> Self {
+        Self::with_id(id)
+    }
+
+    ///
+    /// # Define the area affected by the mask
+    ///
+    /// - `x`       The left, -10% when not defined
+    /// - `y`       The top, -10% when not defined
+    /// - `width`   The width, 120% when not defined
+    /// - `height`  The height, 120% when not defined
+    ///
+    #[must_use]
+    pub fn area<X, Y, W, H>(self, x: X, y: Y, width: W, height: H) -> Self
+    where
+        X: Into<Length>,
+        Y: Into<Length>,
+        W: Into<Length>,
+        H: Into<Length>,
+    {
+        self.attr("x", x.into().to_string())
+            .attr("y", y.into().to_string())
+            .attr("width", width.into().to_string())
+            .attr("height", height.into().to_string())
+    }
+
+    ///
+    /// # Define the coordinate system of the area
+    ///
+    /// - `units` The units, `ObjectBoundingBox` when not defined
+    ///
+    #[must_use]
+    pub fn units(self, units: Units) -> Self {
+        self.attr("maskUnits", units.to_string())
+    }
+
+    ///
+    /// # Define the coordinate system of the content
+    ///
+    /// - `units` The units, `UserSpaceOnUse` when not defined
+    ///
+    #[must_use]
+    pub fn content_units(self, units: Units) -> Self {
+        self.attr("maskContentUnits", units.to_string())
+    }
+
+    ///
+    /// # Define the channel of the content used as opacity
+    ///
+    /// - `kind` The channel, `Luminance` when not defined
+    ///
+    #[must_use]
+    pub fn kind(self, kind: MaskType) -> Self {
+        self.attr("mask-type", kind.to_string())
+    }
+}
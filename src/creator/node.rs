@@ -0,0 +1,283 @@
+use crate::creator::format::{minify, Format};
+use std::fmt::{Display, Formatter};
+
+///
+/// # A child of a node
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum Child {
+    Element(Node),
+    ///
+    /// # A text escaped at the serialization
+    ///
+    Text(String),
+    ///
+    /// # A markup written without escaping
+    ///
+    Raw(String),
+}
+
+///
+/// # An element of the svg document
+///
+/// A node has a tag, an ordered list of attributes and children.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    tag: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Child>,
+}
+
+impl Node {
+    ///
+    /// # Constructor
+    ///
+    /// - `tag` The element name
+    ///
+    #[must_use]
+    pub fn new(tag: &str) -> Self {
+        Self {
+            tag: tag.to_string(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    ///
+    /// # The element name
+    ///
+    #[must_use]
+    pub fn tag(&self) -> &str {
+        self.tag.as_str()
+    }
+
+    ///
+    /// # Get an attribute value
+    ///
+    /// - `name` The attribute name
+    ///
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    ///
+    /// # Set an attribute
+    ///
+    /// An existing attribute keep his position and take the new value.
+    ///
+    /// - `name`    The attribute name
+    /// - `value`   The attribute value
+    ///
+    pub fn set_attribute(&mut self, name: &str, value: &str) -> &mut Self {
+        match self.attributes.iter_mut().find(|(k, _)| k == name) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.attributes.push((name.to_string(), value.to_string())),
+        }
+        self
+    }
+
+    ///
+    /// # Remove an attribute
+    ///
+    /// - `name` The attribute name
+    ///
+    pub fn remove_attribute(&mut self, name: &str) -> Option<String> {
+        let index = self.attributes.iter().position(|(k, _)| k == name)?;
+        Some(self.attributes.remove(index).1)
+    }
+
+    ///
+    /// # The attributes in insertion order
+    ///
+    pub fn attributes(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    ///
+    /// # The children
+    ///
+    #[must_use]
+    pub fn children(&self) -> &[Child] {
+        self.children.as_slice()
+    }
+
+    ///
+    /// # The mutable children
+    ///
+    pub fn children_mut(&mut self) -> &mut Vec<Child> {
+        &mut self.children
+    }
+
+    ///
+    /// # Append an element
+    ///
+    /// - `node` The element to append
+    ///
+    pub fn append(&mut self, node: Self) -> &mut Self {
+        self.children.push(Child::Element(node));
+        self
+    }
+
+    ///
+    /// # Append a text
+    ///
+    /// - `text` The text to append
+    ///
+    pub fn append_text(&mut self, text: &str) -> &mut Self {
+        self.children.push(Child::Text(text.to_string()));
+        self
+    }
+
+    ///
+    /// # Append a markup written without escaping
+    ///
+    /// - `markup` The markup to append
+    ///
+    pub fn append_raw(&mut self, markup: &str) -> &mut Self {
+        self.children.push(Child::Raw(markup.to_string()));
+        self
+    }
+
+    ///
+    /// # The child elements
+    ///
+    pub fn elements(&self) -> impl Iterator<Item = &Self> {
+        self.children.iter().filter_map(|c| match c {
+            Child::Element(n) => Some(n),
+            Child::Text(_) | Child::Raw(_) => None,
+        })
+    }
+
+    ///
+    /// # The mutable child elements
+    ///
+    pub fn elements_mut(&mut self) -> impl Iterator<Item = &mut Self> {
+        self.children.iter_mut().filter_map(|c| match c {
+            Child::Element(n) => Some(n),
+            Child::Text(_) | Child::Raw(_) => None,
+        })
+    }
+
+    ///
+    /// # Find an element by id
+    ///
+    /// - `id` The id to search
+    ///
+    #[must_use]
+    pub fn find(&self, id: &str) -> Option<&Self> {
+        if self.attribute("id") == Some(id) {
+            return Some(self);
+        }
+        self.elements().find_map(|n| n.find(id))
+    }
+
+    ///
+    /// # Find a mutable element by id
+    ///
+    /// - `id` The id to search
+    ///
+    pub fn find_mut(&mut self, id: &str) -> Option<&mut Self> {
+        if self.attribute("id") == Some(id) {
+            return Some(self);
+        }
+        self.elements_mut().find_map(|n| n.find_mut(id))
+    }
+
+    pub(crate) fn child_element_mut(&mut self, index: usize) -> Option<&mut Self> {
+        match self.children.get_mut(index)? {
+            Child::Element(n) => Some(n),
+            Child::Text(_) | Child::Raw(_) => None,
+        }
+    }
+
+    ///
+    /// # Serialize the node
+    ///
+    /// - `out`     The buffer to write
+    /// - `format`  The serialization mode
+    /// - `depth`   The nesting level used by the pretty mode
+    ///
+    pub(crate) fn write(&self, out: &mut String, format: Format, depth: usize) {
+        let indent = match format {
+            Format::Pretty { indent } => Some(" ".repeat(indent * depth)),
+            Format::Compact | Format::Minified => None,
+        };
+        if let Some(i) = &indent {
+            out.push_str(i.as_str());
+        }
+        out.push('<');
+        out.push_str(self.tag.as_str());
+        for (k, v) in &self.attributes {
+            let value = match format {
+                Format::Minified => minify(k, v),
+                Format::Pretty { .. } | Format::Compact => v.to_string(),
+            };
+            out.push_str(format!(" {k}=\"{}\"", escape(value.as_str(), true)).as_str());
+        }
+        if self.children.is_empty() {
+            out.push_str("/>");
+            if indent.is_some() {
+                out.push('\n');
+            }
+            return;
+        }
+        out.push('>');
+        let block =
+            indent.is_some() && self.children.iter().all(|c| matches!(c, Child::Element(_)));
+        if block {
+            out.push('\n');
+        }
+        for child in &self.children {
+            match child {
+                Child::Element(n) if block => n.write(out, format, depth + 1),
+                Child::Element(n) if indent.is_some() => n.write(out, Format::Compact, 0),
+                Child::Element(n) => n.write(out, format, 0),
+                Child::Text(t) => out.push_str(escape(t, false).as_str()),
+                Child::Raw(r) => out.push_str(r.as_str()),
+            }
+        }
+        if let Some(i) = indent.as_ref().filter(|_| block) {
+            out.push_str(i.as_str());
+        }
+        out.push_str(format!("</{}>", self.tag).as_str());
+        if indent.is_some() {
+            out.push('\n');
+        }
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut out = String::new();
+        self.write(&mut out, Format::Compact, 0);
+        f.write_str(out.as_str())
+    }
+}
+
+///
+/// # Escape the xml special characters
+///
+/// - `data`        The data to escape
+/// - `attribute`   Escape the quotes for an attribute value
+///
+pub(crate) fn escape(data: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(data.len());
+    for c in data.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            '\'' if attribute => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,1515 @@
+use crate::creator::error::SvgError;
+use crate::creator::format::{decimal, number_len};
+use crate::creator::geometry::BoundingBox;
+use crate::creator::transform::Matrix;
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+///
+/// # A path command
+///
+/// The coordinates are relative to the current point when `relative` is true.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    MoveTo {
+        relative: bool,
+        x: f64,
+        y: f64,
+    },
+    LineTo {
+        relative: bool,
+        x: f64,
+        y: f64,
+    },
+    HorizontalTo {
+        relative: bool,
+        x: f64,
+    },
+    VerticalTo {
+        relative: bool,
+        y: f64,
+    },
+    CubicTo {
+        relative: bool,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        x: f64,
+        y: f64,
+    },
+    SmoothCubicTo {
+        relative: bool,
+        x2: f64,
+        y2: f64,
+        x: f64,
+        y: f64,
+    },
+    QuadTo {
+        relative: bool,
+        x1: f64,
+        y1: f64,
+        x: f64,
+        y: f64,
+    },
+    SmoothQuadTo {
+        relative: bool,
+        x: f64,
+        y: f64,
+    },
+    ArcTo {
+        relative: bool,
+        rx: f64,
+        ry: f64,
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        x: f64,
+        y: f64,
+    },
+    Close,
+}
+
+impl Command {
+    ///
+    /// # The command letter
+    ///
+    /// Lowercase for the relative commands
+    ///
+    #[must_use]
+    pub const fn letter(&self) -> char {
+        let (letter, relative) = match *self {
+            Self::MoveTo { relative, .. } => ('M', relative),
+            Self::LineTo { relative, .. } => ('L', relative),
+            Self::HorizontalTo { relative, .. } => ('H', relative),
+            Self::VerticalTo { relative, .. } => ('V', relative),
+            Self::CubicTo { relative, .. } => ('C', relative),
+            Self::SmoothCubicTo { relative, .. } => ('S', relative),
+            Self::QuadTo { relative, .. } => ('Q', relative),
+            Self::SmoothQuadTo { relative, .. } => ('T', relative),
+            Self::ArcTo { relative, .. } => ('A', relative),
+            Self::Close => ('Z', false),
+        };
+        if relative {
+            letter.to_ascii_lowercase()
+        } else {
+            letter
+        }
+    }
+
+    ///
+    /// # Check if the coordinates are relative to the current point
+    ///
+    #[must_use]
+    pub const fn is_relative(&self) -> bool {
+        match *self {
+            Self::MoveTo { relative, .. }
+            | Self::LineTo { relative, .. }
+            | Self::HorizontalTo { relative, .. }
+            | Self::VerticalTo { relative, .. }
+            | Self::CubicTo { relative, .. }
+            | Self::SmoothCubicTo { relative, .. }
+            | Self::QuadTo { relative, .. }
+            | Self::SmoothQuadTo { relative, .. }
+            | Self::ArcTo { relative, .. } => relative,
+            Self::Close => false,
+        }
+    }
+
+    ///
+    /// # The command parameters in the order of the path data
+    ///
+    /// The arc flags are 0 or 1.
+    ///
+    #[must_use]
+    pub fn values(&self) -> Vec<f64> {
+        match *self {
+            Self::MoveTo { x, y, .. } | Self::LineTo { x, y, .. } => vec![x, y],
+            Self::SmoothQuadTo { x, y, .. } => vec![x, y],
+            Self::HorizontalTo { x, .. } => vec![x],
+            Self::VerticalTo { y, .. } => vec![y],
+            Self::CubicTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+                ..
+            } => vec![x1, y1, x2, y2, x, y],
+            Self::SmoothCubicTo { x2, y2, x, y, .. } => vec![x2, y2, x, y],
+            Self::QuadTo { x1, y1, x, y, .. } => vec![x1, y1, x, y],
+            Self::ArcTo {
+                rx,
+                ry,
+                rotation,
+                large_arc,
+                sweep,
+                x,
+                y,
+                ..
+            } => vec![
+                rx,
+                ry,
+                rotation,
+                f64::from(u8::from(large_arc)),
+                f64::from(u8::from(sweep)),
+                x,
+                y,
+            ],
+            Self::Close => Vec::new(),
+        }
+    }
+}
+
+///
+/// # A builder of path data
+///
+/// The methods ending with `_to` take absolute coordinates, the ones ending
+/// with `_by` take coordinates relative to the current point. The result is
+/// written in the compact form accepted by `Svg::d`.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathBuilder {
+    commands: Vec<Command>,
+    precision: usize,
+}
+
+impl Default for PathBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PathBuilder {
+    ///
+    /// # Constructor
+    ///
+    /// An empty path written with 6 decimals at most
+    ///
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+            precision: 6,
+        }
+    }
+
+    ///
+    /// # Define the maximum number of decimals
+    ///
+    /// - `precision` The number of decimals
+    ///
+    #[must_use]
+    pub const fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    ///
+    /// # The path commands
+    ///
+    #[must_use]
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    ///
+    /// # Add a command
+    ///
+    /// - `command` The command
+    ///
+    #[must_use]
+    pub fn push(mut self, command: Command) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    ///
+    /// # Start a new sub path
+    ///
+    #[must_use]
+    pub fn move_to(self, x: f64, y: f64) -> Self {
+        self.push(Command::MoveTo {
+            relative: false,
+            x,
+            y,
+        })
+    }
+
+    ///
+    /// # Start a new sub path relatively to the current point
+    ///
+    #[must_use]
+    pub fn move_by(self, dx: f64, dy: f64) -> Self {
+        self.push(Command::MoveTo {
+            relative: true,
+            x: dx,
+            y: dy,
+        })
+    }
+
+    ///
+    /// # Draw a line
+    ///
+    #[must_use]
+    pub fn line_to(self, x: f64, y: f64) -> Self {
+        self.push(Command::LineTo {
+            relative: false,
+            x,
+            y,
+        })
+    }
+
+    ///
+    /// # Draw a line relatively to the current point
+    ///
+    #[must_use]
+    pub fn line_by(self, dx: f64, dy: f64) -> Self {
+        self.push(Command::LineTo {
+            relative: true,
+            x: dx,
+            y: dy,
+        })
+    }
+
+    ///
+    /// # Draw an horizontal line
+    ///
+    #[must_use]
+    pub fn h(self, x: f64) -> Self {
+        self.push(Command::HorizontalTo { relative: false, x })
+    }
+
+    ///
+    /// # Draw an horizontal line relatively to the current point
+    ///
+    #[must_use]
+    pub fn h_by(self, dx: f64) -> Self {
+        self.push(Command::HorizontalTo {
+            relative: true,
+            x: dx,
+        })
+    }
+
+    ///
+    /// # Draw a vertical line
+    ///
+    #[must_use]
+    pub fn v(self, y: f64) -> Self {
+        self.push(Command::VerticalTo { relative: false, y })
+    }
+
+    ///
+    /// # Draw a vertical line relatively to the current point
+    ///
+    #[must_use]
+    pub fn v_by(self, dy: f64) -> Self {
+        self.push(Command::VerticalTo {
+            relative: true,
+            y: dy,
+        })
+    }
+
+    ///
+    /// # Draw a cubic bézier curve
+    ///
+    /// - `c1`  The first control point
+    /// - `c2`  The second control point
+    /// - `end` The end point
+    ///
+    #[must_use]
+    pub fn cubic_to(self, c1: (f64, f64), c2: (f64, f64), end: (f64, f64)) -> Self {
+        self.push(Command::CubicTo {
+            relative: false,
+            x1: c1.0,
+            y1: c1.1,
+            x2: c2.0,
+            y2: c2.1,
+            x: end.0,
+            y: end.1,
+        })
+    }
+
+    ///
+    /// # Draw a cubic bézier curve relatively to the current point
+    ///
+    #[must_use]
+    pub fn cubic_by(self, c1: (f64, f64), c2: (f64, f64), end: (f64, f64)) -> Self {
+        self.push(Command::CubicTo {
+            relative: true,
+            x1: c1.0,
+            y1: c1.1,
+            x2: c2.0,
+            y2: c2.1,
+            x: end.0,
+            y: end.1,
+        })
+    }
+
+    ///
+    /// # Draw a cubic bézier curve reflecting the previous control point
+    ///
+    /// - `c2`  The second control point
+    /// - `end` The end point
+    ///
+    #[must_use]
+    pub fn smooth_cubic_to(self, c2: (f64, f64), end: (f64, f64)) -> Self {
+        self.push(Command::SmoothCubicTo {
+            relative: false,
+            x2: c2.0,
+            y2: c2.1,
+            x: end.0,
+            y: end.1,
+        })
+    }
+
+    ///
+    /// # Draw a smooth cubic bézier curve relatively to the current point
+    ///
+    #[must_use]
+    pub fn smooth_cubic_by(self, c2: (f64, f64), end: (f64, f64)) -> Self {
+        self.push(Command::SmoothCubicTo {
+            relative: true,
+            x2: c2.0,
+            y2: c2.1,
+            x: end.0,
+            y: end.1,
+        })
+    }
+
+    ///
+    /// # Draw a quadratic bézier curve
+    ///
+    /// - `c`   The control point
+    /// - `end` The end point
+    ///
+    #[must_use]
+    pub fn quad_to(self, c: (f64, f64), end: (f64, f64)) -> Self {
+        self.push(Command::QuadTo {
+            relative: false,
+            x1: c.0,
+            y1: c.1,
+            x: end.0,
+            y: end.1,
+        })
+    }
+
+    ///
+    /// # Draw a quadratic bézier curve relatively to the current point
+    ///
+    #[must_use]
+    pub fn quad_by(self, c: (f64, f64), end: (f64, f64)) -> Self {
+        self.push(Command::QuadTo {
+            relative: true,
+            x1: c.0,
+            y1: c.1,
+            x: end.0,
+            y: end.1,
+        })
+    }
+
+    ///
+    /// # Draw a quadratic bézier curve reflecting the previous control point
+    ///
+    #[must_use]
+    pub fn smooth_quad_to(self, x: f64, y: f64) -> Self {
+        self.push(Command::SmoothQuadTo {
+            relative: false,
+            x,
+            y,
+        })
+    }
+
+    ///
+    /// # Draw a smooth quadratic bézier curve relatively to the current point
+    ///
+    #[must_use]
+    pub fn smooth_quad_by(self, dx: f64, dy: f64) -> Self {
+        self.push(Command::SmoothQuadTo {
+            relative: true,
+            x: dx,
+            y: dy,
+        })
+    }
+
+    ///
+    /// # Draw an elliptical arc
+    ///
+    /// - `radius`      The radius on the x and y axis
+    /// - `rotation`    The rotation of the ellipse in degrees
+    /// - `large_arc`   Take the longest arc
+    /// - `sweep`       Draw the arc clockwise
+    /// - `end`         The end point
+    ///
+    #[must_use]
+    pub fn arc_to(
+        self,
+        radius: (f64, f64),
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        end: (f64, f64),
+    ) -> Self {
+        self.push(Command::ArcTo {
+            relative: false,
+            rx: radius.0,
+            ry: radius.1,
+            rotation,
+            large_arc,
+            sweep,
+            x: end.0,
+            y: end.1,
+        })
+    }
+
+    ///
+    /// # Draw an elliptical arc relatively to the current point
+    ///
+    #[must_use]
+    pub fn arc_by(
+        self,
+        radius: (f64, f64),
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        end: (f64, f64),
+    ) -> Self {
+        self.push(Command::ArcTo {
+            relative: true,
+            rx: radius.0,
+            ry: radius.1,
+            rotation,
+            large_arc,
+            sweep,
+            x: end.0,
+            y: end.1,
+        })
+    }
+
+    ///
+    /// # Close the current sub path
+    ///
+    #[must_use]
+    pub fn close(self) -> Self {
+        self.push(Command::Close)
+    }
+
+    ///
+    /// # Add a regular polygon
+    ///
+    /// The first vertex is above the center before the rotation.
+    ///
+    /// - `center`      The center
+    /// - `radius`      The distance between the center and the vertices
+    /// - `sides`       The number of sides
+    /// - `rotation`    The rotation in degrees, clockwise
+    ///
+    #[must_use]
+    pub fn polygon(self, center: (f64, f64), radius: f64, sides: usize, rotation: f64) -> Self {
+        let vertices = (0..sides).map(|i| {
+            let angle = rotation + 360.0 * i as f64 / sides as f64;
+            Self::vertex(center, radius, angle)
+        });
+        self.vertices(vertices)
+    }
+
+    ///
+    /// # Add a star
+    ///
+    /// The first point is above the center before the rotation.
+    ///
+    /// - `center`      The center
+    /// - `outer`       The distance between the center and the points
+    /// - `inner`       The distance between the center and the inner vertices
+    /// - `points`      The number of points
+    /// - `rotation`    The rotation in degrees, clockwise
+    ///
+    #[must_use]
+    pub fn star(
+        self,
+        center: (f64, f64),
+        outer: f64,
+        inner: f64,
+        points: usize,
+        rotation: f64,
+    ) -> Self {
+        let vertices = (0..points * 2).map(|i| {
+            let angle = rotation + 180.0 * i as f64 / points as f64;
+            let radius = if i % 2 == 0 { outer } else { inner };
+            Self::vertex(center, radius, angle)
+        });
+        self.vertices(vertices)
+    }
+
+    ///
+    /// # Add a rectangle with rounded corners
+    ///
+    /// The radius are limited to the half of the size. A negative size
+    /// extends the rectangle to the left or to the top of the origin.
+    ///
+    /// - `origin`  The top left corner
+    /// - `size`    The width and the height
+    /// - `radius`  The radius of the corners on the x and y axis
+    ///
+    #[must_use]
+    pub fn rounded_rect(self, origin: (f64, f64), size: (f64, f64), radius: (f64, f64)) -> Self {
+        let x = origin.0 + size.0.min(0.0);
+        let y = origin.1 + size.1.min(0.0);
+        let (w, h) = (size.0.abs(), size.1.abs());
+        let rx = radius.0.max(0.0).min(w / 2.0);
+        let ry = radius.1.max(0.0).min(h / 2.0);
+        if rx == 0.0 || ry == 0.0 {
+            return self.move_to(x, y).h(x + w).v(y + h).h(x).close();
+        }
+        let r = (rx, ry);
+        self.move_to(x + rx, y)
+            .h(x + w - rx)
+            .arc_to(r, 0.0, false, true, (x + w, y + ry))
+            .v(y + h - ry)
+            .arc_to(r, 0.0, false, true, (x + w - rx, y + h))
+            .h(x + rx)
+            .arc_to(r, 0.0, false, true, (x, y + h - ry))
+            .v(y + ry)
+            .arc_to(r, 0.0, false, true, (x + rx, y))
+            .close()
+    }
+
+    ///
+    /// # The same path with absolute coordinates
+    ///
+    #[must_use]
+    pub fn to_absolute(&self) -> Self {
+        self.map(|command, cursor| vec![cursor.absolute(command)])
+    }
+
+    ///
+    /// # The same path with coordinates relative to the current point
+    ///
+    #[must_use]
+    pub fn to_relative(&self) -> Self {
+        self.map(|command, cursor| vec![cursor.relative(command)])
+    }
+
+    ///
+    /// # The same path without the shorthand commands
+    ///
+    /// The coordinates become absolute, `H` and `V` are written as lines, `S`
+    /// and `T` as curves with their reflected control point.
+    ///
+    #[must_use]
+    pub fn expand_shorthands(&self) -> Self {
+        self.map(|command, cursor| vec![cursor.expand(cursor.absolute(command))])
+    }
+
+    ///
+    /// # The same path with the elliptical arcs drawn as cubic béziers
+    ///
+    /// The coordinates become absolute.
+    ///
+    #[must_use]
+    pub fn arcs_to_cubics(&self) -> Self {
+        self.map(|command, cursor| match cursor.absolute(command) {
+            arc @ Command::ArcTo { .. } => arc_to_cubics(cursor.current, arc),
+            other => vec![other],
+        })
+    }
+
+    ///
+    /// # The canonical form of the path
+    ///
+    /// Only absolute moves, lines, cubic béziers and closes.
+    ///
+    #[must_use]
+    pub fn normalize(&self) -> Self {
+        self.map(
+            |command, cursor| match cursor.expand(cursor.absolute(command)) {
+                arc @ Command::ArcTo { .. } => arc_to_cubics(cursor.current, arc),
+                other => vec![quad_to_cubic(cursor.current, other)],
+            },
+        )
+    }
+
+    ///
+    /// # The path with a transformation applied to its coordinates
+    ///
+    /// The path is normalized first, the arcs become cubic béziers.
+    ///
+    /// - `matrix` The transformation
+    ///
+    #[must_use]
+    pub fn transform(&self, matrix: Matrix) -> Self {
+        self.normalize().map(|command, _| {
+            vec![match command {
+                Command::MoveTo { x, y, .. } => {
+                    let (x, y) = matrix.apply((x, y));
+                    Command::MoveTo {
+                        relative: false,
+                        x,
+                        y,
+                    }
+                }
+                Command::LineTo { x, y, .. } => {
+                    let (x, y) = matrix.apply((x, y));
+                    Command::LineTo {
+                        relative: false,
+                        x,
+                        y,
+                    }
+                }
+                Command::CubicTo {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x,
+                    y,
+                    ..
+                } => {
+                    let (x1, y1) = matrix.apply((x1, y1));
+                    let (x2, y2) = matrix.apply((x2, y2));
+                    let (x, y) = matrix.apply((x, y));
+                    Command::CubicTo {
+                        relative: false,
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        x,
+                        y,
+                    }
+                }
+                other => other,
+            }]
+        })
+    }
+
+    ///
+    /// # The bounding box of the path
+    ///
+    /// The extrema of the curves and of the arcs are included. None for an
+    /// empty path.
+    ///
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let mut bounds: Option<BoundingBox> = None;
+        let mut include = |p: (f64, f64)| {
+            bounds = Some(bounds.map_or(BoundingBox::point(p), |b| b.include(p)));
+        };
+        let mut current = (0.0, 0.0);
+        let mut start = (0.0, 0.0);
+        let curves = self.map(
+            |command, cursor| match cursor.expand(cursor.absolute(command)) {
+                arc @ Command::ArcTo { .. } => vec![arc],
+                other => vec![quad_to_cubic(cursor.current, other)],
+            },
+        );
+        for command in curves.commands() {
+            match *command {
+                Command::MoveTo { x, y, .. } => {
+                    include((x, y));
+                    current = (x, y);
+                    start = current;
+                }
+                Command::LineTo { x, y, .. } => {
+                    include((x, y));
+                    current = (x, y);
+                }
+                Command::CubicTo {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x,
+                    y,
+                    ..
+                } => {
+                    let xs = [current.0, x1, x2, x];
+                    let ys = [current.1, y1, y2, y];
+                    include((x, y));
+                    for t in extrema(xs).into_iter().chain(extrema(ys)).flatten() {
+                        include((cubic(xs, t), cubic(ys, t)));
+                    }
+                    current = (x, y);
+                }
+                arc @ Command::ArcTo { x, y, .. } => {
+                    include((x, y));
+                    if let Some(ellipse) = Ellipse::new(current, arc) {
+                        for t in ellipse.extrema() {
+                            include(ellipse.point(t));
+                        }
+                    }
+                    current = (x, y);
+                }
+                _ => current = start,
+            }
+        }
+        bounds
+    }
+
+    ///
+    /// # The same path with each command in its shortest form
+    ///
+    /// A command is written relative to the current point when it is shorter
+    /// than its absolute form.
+    ///
+    #[must_use]
+    pub fn shortest(&self) -> Self {
+        self.map(|command, cursor| {
+            let absolute = cursor.absolute(command);
+            let relative = cursor.relative(command);
+            if self.values_len(relative) < self.values_len(absolute) {
+                vec![relative]
+            } else {
+                vec![absolute]
+            }
+        })
+    }
+
+    ///
+    /// # Rewrite each command knowing the current point
+    ///
+    fn map<F>(&self, mut f: F) -> Self
+    where
+        F: FnMut(Command, &Cursor) -> Vec<Command>,
+    {
+        let mut cursor = Cursor::default();
+        let mut commands = Vec::with_capacity(self.commands.len());
+        for command in &self.commands {
+            commands.extend(f(*command, &cursor));
+            cursor.advance(*command);
+        }
+        Self {
+            commands,
+            precision: self.precision,
+        }
+    }
+
+    fn values_len(&self, command: Command) -> usize {
+        command
+            .values()
+            .iter()
+            .map(|v| self.number(*v).len() + 1)
+            .sum()
+    }
+
+    fn vertex(center: (f64, f64), radius: f64, angle: f64) -> (f64, f64) {
+        let radians = (angle - 90.0) * PI / 180.0;
+        (
+            radius.mul_add(radians.cos(), center.0),
+            radius.mul_add(radians.sin(), center.1),
+        )
+    }
+
+    fn vertices<I: Iterator<Item = (f64, f64)>>(self, mut vertices: I) -> Self {
+        let Some((x, y)) = vertices.next() else {
+            return self;
+        };
+        vertices
+            .fold(self.move_to(x, y), |path, (x, y)| path.line_to(x, y))
+            .close()
+    }
+
+    fn number(&self, value: f64) -> String {
+        decimal(value, self.precision)
+    }
+}
+
+impl Display for PathBuilder {
+    ///
+    /// # The compact path data
+    ///
+    /// A command letter is omitted when it repeats the previous one and the
+    /// numbers are only separated when needed.
+    ///
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut previous_letter: Option<char> = None;
+        let mut previous_number: Option<String> = None;
+        for command in &self.commands {
+            let letter = command.letter();
+            let implicit = previous_letter == Some(letter) && !matches!(letter, 'M' | 'm');
+            if !implicit || matches!(letter, 'Z' | 'z') {
+                write!(f, "{letter}")?;
+                previous_number = None;
+            }
+            previous_letter = Some(letter);
+            for value in command.values() {
+                let number = self.number(value);
+                if let Some(previous) = &previous_number {
+                    let glued = number.starts_with('-')
+                        || (number.starts_with('.') && previous.contains('.'));
+                    if !glued {
+                        f.write_str(" ")?;
+                    }
+                }
+                f.write_str(number.as_str())?;
+                previous_number = Some(number);
+            }
+        }
+        Ok(())
+    }
+}
+
+///
+/// # The state needed to resolve a command
+///
+#[derive(Default)]
+struct Cursor {
+    current: (f64, f64),
+    start: (f64, f64),
+    cubic: Option<(f64, f64)>,
+    quad: Option<(f64, f64)>,
+}
+
+impl Cursor {
+    fn absolute(&self, command: Command) -> Command {
+        if !command.is_relative() {
+            return command;
+        }
+        let (cx, cy) = self.current;
+        match command {
+            Command::MoveTo { x, y, .. } => Command::MoveTo {
+                relative: false,
+                x: cx + x,
+                y: cy + y,
+            },
+            Command::LineTo { x, y, .. } => Command::LineTo {
+                relative: false,
+                x: cx + x,
+                y: cy + y,
+            },
+            Command::HorizontalTo { x, .. } => Command::HorizontalTo {
+                relative: false,
+                x: cx + x,
+            },
+            Command::VerticalTo { y, .. } => Command::VerticalTo {
+                relative: false,
+                y: cy + y,
+            },
+            Command::CubicTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+                ..
+            } => Command::CubicTo {
+                relative: false,
+                x1: cx + x1,
+                y1: cy + y1,
+                x2: cx + x2,
+                y2: cy + y2,
+                x: cx + x,
+                y: cy + y,
+            },
+            Command::SmoothCubicTo { x2, y2, x, y, .. } => Command::SmoothCubicTo {
+                relative: false,
+                x2: cx + x2,
+                y2: cy + y2,
+                x: cx + x,
+                y: cy + y,
+            },
+            Command::QuadTo { x1, y1, x, y, .. } => Command::QuadTo {
+                relative: false,
+                x1: cx + x1,
+                y1: cy + y1,
+                x: cx + x,
+                y: cy + y,
+            },
+            Command::SmoothQuadTo { x, y, .. } => Command::SmoothQuadTo {
+                relative: false,
+                x: cx + x,
+                y: cy + y,
+            },
+            Command::ArcTo {
+                rx,
+                ry,
+                rotation,
+                large_arc,
+                sweep,
+                x,
+                y,
+                ..
+            } => Command::ArcTo {
+                relative: false,
+                rx,
+                ry,
+                rotation,
+                large_arc,
+                sweep,
+                x: cx + x,
+                y: cy + y,
+            },
+            Command::Close => Command::Close,
+        }
+    }
+
+    fn relative(&self, command: Command) -> Command {
+        let (cx, cy) = self.current;
+        match self.absolute(command) {
+            Command::MoveTo { x, y, .. } => Command::MoveTo {
+                relative: true,
+                x: x - cx,
+                y: y - cy,
+            },
+            Command::LineTo { x, y, .. } => Command::LineTo {
+                relative: true,
+                x: x - cx,
+                y: y - cy,
+            },
+            Command::HorizontalTo { x, .. } => Command::HorizontalTo {
+                relative: true,
+                x: x - cx,
+            },
+            Command::VerticalTo { y, .. } => Command::VerticalTo {
+                relative: true,
+                y: y - cy,
+            },
+            Command::CubicTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+                ..
+            } => Command::CubicTo {
+                relative: true,
+                x1: x1 - cx,
+                y1: y1 - cy,
+                x2: x2 - cx,
+                y2: y2 - cy,
+                x: x - cx,
+                y: y - cy,
+            },
+            Command::SmoothCubicTo { x2, y2, x, y, .. } => Command::SmoothCubicTo {
+                relative: true,
+                x2: x2 - cx,
+                y2: y2 - cy,
+                x: x - cx,
+                y: y - cy,
+            },
+            Command::QuadTo { x1, y1, x, y, .. } => Command::QuadTo {
+                relative: true,
+                x1: x1 - cx,
+                y1: y1 - cy,
+                x: x - cx,
+                y: y - cy,
+            },
+            Command::SmoothQuadTo { x, y, .. } => Command::SmoothQuadTo {
+                relative: true,
+                x: x - cx,
+                y: y - cy,
+            },
+            Command::ArcTo {
+                rx,
+                ry,
+                rotation,
+                large_arc,
+                sweep,
+                x,
+                y,
+                ..
+            } => Command::ArcTo {
+                relative: true,
+                rx,
+                ry,
+                rotation,
+                large_arc,
+                sweep,
+                x: x - cx,
+                y: y - cy,
+            },
+            Command::Close => Command::Close,
+        }
+    }
+
+    ///
+    /// # Replace an absolute shorthand by the full command
+    ///
+    fn expand(&self, command: Command) -> Command {
+        let (cx, cy) = self.current;
+        let reflect = |control: Option<(f64, f64)>| {
+            control.map_or(self.current, |(x, y)| (2.0 * cx - x, 2.0 * cy - y))
+        };
+        match command {
+            Command::HorizontalTo { x, .. } => Command::LineTo {
+                relative: false,
+                x,
+                y: cy,
+            },
+            Command::VerticalTo { y, .. } => Command::LineTo {
+                relative: false,
+                x: cx,
+                y,
+            },
+            Command::SmoothCubicTo { x2, y2, x, y, .. } => {
+                let (x1, y1) = reflect(self.cubic);
+                Command::CubicTo {
+                    relative: false,
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x,
+                    y,
+                }
+            }
+            Command::SmoothQuadTo { x, y, .. } => {
+                let (x1, y1) = reflect(self.quad);
+                Command::QuadTo {
+                    relative: false,
+                    x1,
+                    y1,
+                    x,
+                    y,
+                }
+            }
+            other => other,
+        }
+    }
+
+    ///
+    /// # Move to the end of a command
+    ///
+    fn advance(&mut self, command: Command) {
+        let command = self.expand(self.absolute(command));
+        let (cubic, quad) = match command {
+            Command::CubicTo { x2, y2, .. } => (Some((x2, y2)), None),
+            Command::QuadTo { x1, y1, .. } => (None, Some((x1, y1))),
+            _ => (None, None),
+        };
+        self.current = match command {
+            Command::MoveTo { x, y, .. } => {
+                self.start = (x, y);
+                (x, y)
+            }
+            Command::LineTo { x, y, .. }
+            | Command::CubicTo { x, y, .. }
+            | Command::QuadTo { x, y, .. }
+            | Command::ArcTo { x, y, .. } => (x, y),
+            _ => self.start,
+        };
+        self.cubic = cubic;
+        self.quad = quad;
+    }
+}
+
+///
+/// # A coordinate of a cubic bézier curve
+///
+fn cubic(p: [f64; 4], t: f64) -> f64 {
+    let u = 1.0 - t;
+    (u * u * u).mul_add(
+        p[0],
+        (3.0 * u * u * t).mul_add(p[1], (3.0 * u * t * t).mul_add(p[2], t * t * t * p[3])),
+    )
+}
+
+///
+/// # The parameters of the extrema of a cubic bézier coordinate
+///
+fn extrema(p: [f64; 4]) -> [Option<f64>; 2] {
+    let a = 3.0f64.mul_add(p[1] - p[2], p[3] - p[0]);
+    let b = 2.0 * 2.0f64.mul_add(-p[1], p[0] + p[2]);
+    let c = p[1] - p[0];
+    let inside = |t: f64| (t > 0.0 && t < 1.0).then_some(t);
+    if a.abs() < 1e-12 {
+        if b.abs() < 1e-12 {
+            return [None, None];
+        }
+        return [inside(-c / b), None];
+    }
+    let discriminant = b.mul_add(b, -4.0 * a * c);
+    if discriminant < 0.0 {
+        return [None, None];
+    }
+    let root = discriminant.sqrt();
+    [
+        inside((-b + root) / (2.0 * a)),
+        inside((-b - root) / (2.0 * a)),
+    ]
+}
+
+///
+/// # Raise an absolute quadratic bézier to a cubic one
+///
+/// The other commands are returned unchanged.
+///
+const fn quad_to_cubic(from: (f64, f64), command: Command) -> Command {
+    let Command::QuadTo { x1, y1, x, y, .. } = command else {
+        return command;
+    };
+    let (px, py) = from;
+    Command::CubicTo {
+        relative: false,
+        x1: (2.0 * x1).mul_add(1.0 / 3.0, px / 3.0),
+        y1: (2.0 * y1).mul_add(1.0 / 3.0, py / 3.0),
+        x2: (2.0 * x1).mul_add(1.0 / 3.0, x / 3.0),
+        y2: (2.0 * y1).mul_add(1.0 / 3.0, y / 3.0),
+        x,
+        y,
+    }
+}
+
+///
+/// # The center parameterization of an elliptical arc
+///
+struct Ellipse {
+    cx: f64,
+    cy: f64,
+    rx: f64,
+    ry: f64,
+    sin: f64,
+    cos: f64,
+    theta: f64,
+    delta: f64,
+}
+
+impl Ellipse {
+    ///
+    /// # Constructor
+    ///
+    /// None when the arc is a line or when its end is its start
+    ///
+    /// - `from`    The current point
+    /// - `arc`     The absolute arc
+    ///
+    fn new(from: (f64, f64), arc: Command) -> Option<Self> {
+        let Command::ArcTo {
+            rx,
+            ry,
+            rotation,
+            large_arc,
+            sweep,
+            x,
+            y,
+            ..
+        } = arc
+        else {
+            return None;
+        };
+        let (x0, y0) = from;
+        if (x0 - x).abs() < f64::EPSILON && (y0 - y).abs() < f64::EPSILON {
+            return None;
+        }
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if rx == 0.0 || ry == 0.0 {
+            return None;
+        }
+        let (sin, cos) = rotation.to_radians().sin_cos();
+        let dx = (x0 - x) / 2.0;
+        let dy = (y0 - y) / 2.0;
+        let x1 = cos.mul_add(dx, sin * dy);
+        let y1 = (-sin).mul_add(dx, cos * dy);
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+        let numerator = (rx * rx).mul_add(ry * ry, -(rx * rx) * (y1 * y1)) - (ry * ry) * (x1 * x1);
+        let denominator = (rx * rx).mul_add(y1 * y1, (ry * ry) * (x1 * x1));
+        let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+        if large_arc == sweep {
+            coefficient = -coefficient;
+        }
+        let cx1 = coefficient * rx * y1 / ry;
+        let cy1 = -coefficient * ry * x1 / rx;
+        let angle = |ux: f64, uy: f64| uy.atan2(ux);
+        let theta = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - theta;
+        if sweep && delta < 0.0 {
+            delta += 2.0 * PI;
+        } else if !sweep && delta > 0.0 {
+            delta -= 2.0 * PI;
+        }
+        Some(Self {
+            cx: cos.mul_add(cx1, -sin * cy1) + (x0 + x) / 2.0,
+            cy: sin.mul_add(cx1, cos * cy1) + (y0 + y) / 2.0,
+            rx,
+            ry,
+            sin,
+            cos,
+            theta,
+            delta,
+        })
+    }
+
+    ///
+    /// # The point at an angle
+    ///
+    fn point(&self, t: f64) -> (f64, f64) {
+        let (s, c) = t.sin_cos();
+        (
+            self.cos.mul_add(self.rx * c, -self.sin * self.ry * s) + self.cx,
+            self.sin.mul_add(self.rx * c, self.cos * self.ry * s) + self.cy,
+        )
+    }
+
+    ///
+    /// # The tangent at an angle
+    ///
+    fn derivative(&self, t: f64) -> (f64, f64) {
+        let (s, c) = t.sin_cos();
+        (
+            self.cos.mul_add(-self.rx * s, -self.sin * self.ry * c),
+            self.sin.mul_add(-self.rx * s, self.cos * self.ry * c),
+        )
+    }
+
+    ///
+    /// # The angles of the extrema of the coordinates inside the arc
+    ///
+    fn extrema(&self) -> Vec<f64> {
+        let x = (-self.ry * self.sin).atan2(self.rx * self.cos);
+        let y = (self.ry * self.cos).atan2(self.rx * self.sin);
+        [x, x + PI, y, y + PI]
+            .into_iter()
+            .filter(|t| {
+                let swept = (t - self.theta) * self.delta.signum();
+                swept.rem_euclid(2.0 * PI) < self.delta.abs()
+            })
+            .collect()
+    }
+}
+
+///
+/// # Approximate an absolute arc with cubic béziers
+///
+/// The arc is split in segments of 90 degrees at most.
+///
+fn arc_to_cubics(from: (f64, f64), arc: Command) -> Vec<Command> {
+    let Command::ArcTo { x, y, .. } = arc else {
+        return vec![arc];
+    };
+    let (x0, y0) = from;
+    if (x0 - x).abs() < f64::EPSILON && (y0 - y).abs() < f64::EPSILON {
+        return Vec::new();
+    }
+    let Some(ellipse) = Ellipse::new(from, arc) else {
+        return vec![Command::LineTo {
+            relative: false,
+            x,
+            y,
+        }];
+    };
+    let segments = (ellipse.delta.abs() / (PI / 2.0) - 1e-9).ceil().max(1.0);
+    let step = ellipse.delta / segments;
+    let kappa = 4.0 / 3.0 * (step / 4.0).tan();
+    let mut commands = Vec::new();
+    let mut t = ellipse.theta;
+    for i in 0..segments as usize {
+        let next = t + step;
+        let (px, py) = ellipse.point(t);
+        let (dx1, dy1) = ellipse.derivative(t);
+        let (dx2, dy2) = ellipse.derivative(next);
+        let (ex, ey) = if i + 1 == segments as usize {
+            (x, y)
+        } else {
+            ellipse.point(next)
+        };
+        commands.push(Command::CubicTo {
+            relative: false,
+            x1: kappa.mul_add(dx1, px),
+            y1: kappa.mul_add(dy1, py),
+            x2: (-kappa).mul_add(dx2, ex),
+            y2: (-kappa).mul_add(dy2, ey),
+            x: ex,
+            y: ey,
+        });
+        t = next;
+    }
+    commands
+}
+
+impl From<Vec<Command>> for PathBuilder {
+    fn from(commands: Vec<Command>) -> Self {
+        Self {
+            commands,
+            ..Self::new()
+        }
+    }
+}
+
+impl FromStr for PathBuilder {
+    type Err = SvgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s).map(Self::from)
+    }
+}
+
+///
+/// # Parse path data
+///
+/// The implicit commands following a move are returned as lines.
+///
+/// - `data` The content of a `d` attribute
+///
+/// # Errors
+///
+/// `SvgError::InvalidPath` with the offset of the first syntax error
+///
+pub fn parse(data: &str) -> Result<Vec<Command>, SvgError> {
+    Parser { data, offset: 0 }.parse()
+}
+
+struct Parser<'a> {
+    data: &'a str,
+    offset: usize,
+}
+
+impl Parser<'_> {
+    fn parse(&mut self) -> Result<Vec<Command>, SvgError> {
+        let mut commands = Vec::new();
+        self.skip_spaces();
+        let mut letter: Option<char> = None;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphabetic() {
+                if !"MmLlHhVvCcSsQqTtAaZz".contains(c) {
+                    return Err(self.error(format!("unknown command {c}")));
+                }
+                if commands.is_empty() && !matches!(c, 'M' | 'm') {
+                    return Err(self.error("the path must start with a move".to_string()));
+                }
+                self.offset += 1;
+                letter = Some(c);
+                if matches!(c, 'Z' | 'z') {
+                    commands.push(Command::Close);
+                    self.skip_spaces();
+                    continue;
+                }
+            } else {
+                match letter {
+                    None => return Err(self.error("expected a move".to_string())),
+                    Some('Z' | 'z') => return Err(self.error("expected a command".to_string())),
+                    Some(_) => {}
+                }
+            }
+            let Some(current) = letter else {
+                break;
+            };
+            commands.push(self.command(current)?);
+            if matches!(current, 'M' | 'm') {
+                letter = Some(if current == 'M' { 'L' } else { 'l' });
+            }
+            self.skip_spaces();
+        }
+        Ok(commands)
+    }
+
+    fn command(&mut self, letter: char) -> Result<Command, SvgError> {
+        let relative = letter.is_ascii_lowercase();
+        Ok(match letter.to_ascii_uppercase() {
+            'M' => Command::MoveTo {
+                relative,
+                x: self.number()?,
+                y: self.number()?,
+            },
+            'L' => Command::LineTo {
+                relative,
+                x: self.number()?,
+                y: self.number()?,
+            },
+            'H' => Command::HorizontalTo {
+                relative,
+                x: self.number()?,
+            },
+            'V' => Command::VerticalTo {
+                relative,
+                y: self.number()?,
+            },
+            'C' => Command::CubicTo {
+                relative,
+                x1: self.number()?,
+                y1: self.number()?,
+                x2: self.number()?,
+                y2: self.number()?,
+                x: self.number()?,
+                y: self.number()?,
+            },
+            'S' => Command::SmoothCubicTo {
+                relative,
+                x2: self.number()?,
+                y2: self.number()?,
+                x: self.number()?,
+                y: self.number()?,
+            },
+            'Q' => Command::QuadTo {
+                relative,
+                x1: self.number()?,
+                y1: self.number()?,
+                x: self.number()?,
+                y: self.number()?,
+            },
+            'T' => Command::SmoothQuadTo {
+                relative,
+                x: self.number()?,
+                y: self.number()?,
+            },
+            _ => Command::ArcTo {
+                relative,
+                rx: self.number()?,
+                ry: self.number()?,
+                rotation: self.number()?,
+                large_arc: self.flag()?,
+                sweep: self.flag()?,
+                x: self.number()?,
+                y: self.number()?,
+            },
+        })
+    }
+
+    fn number(&mut self) -> Result<f64, SvgError> {
+        self.skip_separator();
+        let rest = &self.data[self.offset..];
+        let len = number_len(rest);
+        if len == 0 {
+            return Err(self.error("expected a number".to_string()));
+        }
+        let value = rest[..len]
+            .parse()
+            .map_err(|_| self.error("invalid number".to_string()))?;
+        self.offset += len;
+        Ok(value)
+    }
+
+    fn flag(&mut self) -> Result<bool, SvgError> {
+        self.skip_separator();
+        let flag = match self.peek() {
+            Some('0') => false,
+            Some('1') => true,
+            _ => return Err(self.error("expected a flag".to_string())),
+        };
+        self.offset += 1;
+        Ok(flag)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.data[self.offset..].chars().next()
+    }
+
+    fn skip_spaces(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_whitespace()) {
+            self.offset += c.len_utf8();
+        }
+    }
+
+    fn skip_separator(&mut self) {
+        self.skip_spaces();
+        if self.peek() == Some(',') {
+            self.offset += 1;
+            self.skip_spaces();
+        }
+    }
+
+    fn error(&self, message: String) -> SvgError {
+        SvgError::InvalidPath {
+            offset: self.offset,
+            message,
+        }
+    }
+}
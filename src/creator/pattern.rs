@@ -0,0 +1,200 @@
+use crate::creator::color::Color;
+use crate::creator::definition::{Drawing, Template, Units};
+use crate::creator::length::Length;
+use crate::creator::transform::Transform;
+use crate::creator::viewport::ViewBox;
+
+///
+/// # The kind of the pattern definitions
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tiled;
+
+impl Drawing for Tiled {
+    const TAG: &'static str = "pattern";
+}
+
+///
+/// # A pattern definition
+///
+/// The tile is drawn with `content` and repeated to fill the elements
+/// referencing the pattern with `Paint::Url`.
+///
+pub type Pattern = Template<Tiled>;
+
+impl Pattern {
+    ///
+    /// # Constructor
+    ///
+    /// - `id`      The id of the pattern
+    /// - `width`   The width of the tile
+    /// - `height`  The height of the tile
+    ///
+    #[must_use]
+    pub fn new<W: Into<Length>, H: Into<Length>>(id: &str, width: W, height: H) -> Self {
+        Self::with_id(id)
+            .attr("width", width.into().to_string())
+            .attr("height", height.into().to_string())
+    }
+
+    ///
+    /// # Define the position of the first tile
+    ///
+    #[must_use]
+    pub fn position<X: Into<Length>, Y: Into<Length>>(self, x: X, y: Y) -> Self {
+        self.attr("x", x.into().to_string())
+            .attr("y", y.into().to_string())
+    }
+
+    ///
+    /// # Define the coordinate system of the tile position and size
+    ///
+    /// - `units` The units, `ObjectBoundingBox` when not defined
+    ///
+    #[must_use]
+    pub fn units(self, units: Units) -> Self {
+        self.attr("patternUnits", units.to_string())
+    }
+
+    ///
+    /// # Define the coordinate system of the tile content
+    ///
+    /// - `units` The units, `UserSpaceOnUse` when not defined
+    ///
+    #[must_use]
+    pub fn content_units(self, units: Units) -> Self {
+        self.attr("patternContentUnits", units.to_string())
+    }
+
+    ///
+    /// # Transform the whole pattern
+    ///
+    /// - `transform` The transformation
+    ///
+    #[must_use]
+    pub fn transform(self, transform: &Transform) -> Self {
+        self.attr("patternTransform", transform.to_string())
+    }
+
+    ///
+    /// # Define the user space of the tile content
+    ///
+    /// - `view_box` The area of the content shown in a tile
+    ///
+    #[must_use]
+    pub fn view_box(self, view_box: ViewBox) -> Self {
+        self.attr("viewBox", view_box.to_string())
+    }
+
+    ///
+    /// # Diagonal lines
+    ///
+    /// - `id`      The id of the pattern
+    /// - `color`   The color of the lines
+    /// - `spacing` The distance between two lines
+    /// - `width`   The width of the lines
+    ///
+    #[must_use]
+    pub fn hatch(id: &str, color: Color, spacing: f64, width: f64) -> Self {
+        Self::lines(id, color, spacing, width).transform(&Transform::new().rotate(45.0))
+    }
+
+    ///
+    /// # Two crossing sets of diagonal lines
+    ///
+    /// - `id`      The id of the pattern
+    /// - `color`   The color of the lines
+    /// - `spacing` The distance between two lines
+    /// - `width`   The width of the lines
+    ///
+    #[must_use]
+    pub fn cross_hatch(id: &str, color: Color, spacing: f64, width: f64) -> Self {
+        Self::lines(id, color, spacing, width)
+            .content(|tile| {
+                tile.line(0.0, spacing / 2.0, spacing, spacing / 2.0, color)
+                    .stroke_width(width)
+                    .close();
+            })
+            .transform(&Transform::new().rotate(45.0))
+    }
+
+    ///
+    /// # Vertical stripes
+    ///
+    /// - `id`      The id of the pattern
+    /// - `color`   The color of the stripes
+    /// - `spacing` The distance between the start of two stripes
+    /// - `width`   The width of the stripes
+    ///
+    #[must_use]
+    pub fn stripes(id: &str, color: Color, spacing: f64, width: f64) -> Self {
+        Self::new(id, spacing, spacing)
+            .units(Units::UserSpaceOnUse)
+            .content(|tile| {
+                tile.rect()
+                    .width(width)
+                    .height(spacing)
+                    .fill(color)
+                    .close_rect();
+            })
+    }
+
+    ///
+    /// # Dots on a square grid
+    ///
+    /// - `id`      The id of the pattern
+    /// - `color`   The color of the dots
+    /// - `spacing` The distance between the centers of two dots
+    /// - `radius`  The radius of the dots
+    ///
+    #[must_use]
+    pub fn dots(id: &str, color: Color, spacing: f64, radius: f64) -> Self {
+        Self::new(id, spacing, spacing)
+            .units(Units::UserSpaceOnUse)
+            .content(|tile| {
+                tile.circle()
+                    .cx(spacing / 2.0)
+                    .cy(spacing / 2.0)
+                    .r(radius)
+                    .fill(color)
+                    .close_circle();
+            })
+    }
+
+    ///
+    /// # Alternating squares
+    ///
+    /// - `id`      The id of the pattern
+    /// - `color`   The color of the painted squares, the others are transparent
+    /// - `size`    The side of a square
+    ///
+    #[must_use]
+    pub fn checkerboard(id: &str, color: Color, size: f64) -> Self {
+        Self::new(id, size * 2.0, size * 2.0)
+            .units(Units::UserSpaceOnUse)
+            .content(|tile| {
+                tile.rect()
+                    .width(size)
+                    .height(size)
+                    .fill(color)
+                    .close_rect()
+                    .rect()
+                    .x(size)
+                    .y(size)
+                    .width(size)
+                    .height(size)
+                    .fill(color)
+                    .close_rect();
+            })
+    }
+
+    fn lines(id: &str, color: Color, spacing: f64, width: f64) -> Self {
+        Self::new(id, spacing, spacing)
+            .units(Units::UserSpaceOnUse)
+            .content(|tile| {
+                tile.line(spacing / 2.0, 0.0, spacing / 2.0, spacing, color)
+                    .stroke_width(width)
+                    .close();
+            })
+    }
+}
@@ -1,75 +1,194 @@
+use crate::creator::animation::{Animated, Animation};
+use crate::creator::color::{Color, Paint};
+use crate::creator::definition::Definition;
+use crate::creator::effect;
+use crate::creator::element::Document;
+use crate::creator::error::SvgError;
+use crate::creator::fetch::{OfflineFetcher, ResourceFetcher};
+use crate::creator::filter;
+use crate::creator::filter::Filter;
+use crate::creator::format::{decimal, Format};
+use crate::creator::geometry::{bounding_box, flatten, length, locate, referenced, BoundingBox};
+use crate::creator::image::data_uri;
+use crate::creator::length::Length;
+use crate::creator::node::{Child, Node};
+use crate::creator::path::parse;
+use crate::creator::transform::{Matrix, Transform};
+use crate::creator::validate::{validate, Diagnostic};
+use crate::creator::viewport::{PreserveAspectRatio, ViewBox};
+use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+#[derive(Clone)]
 pub struct Svg {
-    svg: String,
-    view_box: String,
-    width: f64,
-    height: f64,
-    width_unit: String,
-    height_unit: String,
+    root: Option<Node>,
+    open: Vec<usize>,
+    error: Option<SvgError>,
+    defs: Vec<Node>,
+    fetcher: Arc<dyn ResourceFetcher>,
+    format: Format,
+    view_box: ViewBox,
+    aspect: Option<PreserveAspectRatio>,
+    width: Length,
+    height: Length,
     id: String,
 }
 
+impl Debug for Svg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Svg")
+            .field("root", &self.root)
+            .field("open", &self.open)
+            .field("error", &self.error)
+            .field("defs", &self.defs)
+            .field("format", &self.format)
+            .field("view_box", &self.view_box)
+            .field("aspect", &self.aspect)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.to_xml().as_str())
+    }
+}
+
 impl Svg {
     ///
     /// # Constructor
     ///
     /// - `width`       The svg width
     /// - `height`      The svg height
-    /// - `view_box`    The svg viewBox
+    /// - `view`        The svg viewBox, a string is parsed and reported on save when invalid
+    /// - `id`          The svg id
     ///
     #[must_use]
-    pub fn new(
-        width: f64,
-        width_unit: &str,
-        height: f64,
-        height_unit: &str,
-        view: &str,
-        id: &str,
-    ) -> Self {
+    pub fn new<W, H, V>(width: W, height: H, view: V, id: &str) -> Self
+    where
+        W: Into<Length>,
+        H: Into<Length>,
+        V: TryInto<ViewBox>,
+        SvgError: From<V::Error>,
+    {
+        let (view_box, error) = match view.try_into() {
+            Ok(v) => (v, None),
+            Err(e) => (ViewBox::default(), Some(SvgError::from(e))),
+        };
         Self {
-            svg: String::new(),
-            view_box: view.to_string(),
-            width,
-            height,
-            width_unit: width_unit.to_string(),
-            height_unit: height_unit.to_string(),
+            root: None,
+            open: Vec::new(),
+            error,
+            defs: Vec::new(),
+            fetcher: Arc::new(OfflineFetcher::default()),
+            format: Format::default(),
+            view_box,
+            aspect: None,
+            width: width.into(),
+            height: height.into(),
             id: id.to_string(),
         }
     }
 
+    ///
+    /// # Start an animate element
+    ///
+    /// The attributes are written as is with `attribute_name`, `values`, `dur`
+    /// and `repeat_count`, the element is ended by `close`. Prefer `animation`
+    /// which checks the values and the timing.
+    ///
     pub fn animate(&mut self) -> &mut Self {
-        self.svg.push_str("<animate ");
+        self.open("animate");
         self
     }
+
+    ///
+    /// # Add an animation to the current element
+    ///
+    /// - `animation` The animate, animateTransform, animateMotion or set
+    ///
+    pub fn animation<K: Animated>(&mut self, mut animation: Animation<K>) -> &mut Self {
+        if let Some(error) = animation.take_error() {
+            self.fail(error);
+        }
+        self.append(animation.to_node())
+    }
+
+    ///
+    /// # Set the values of an animation
+    ///
+    /// - `value` The values separated by semicolons
+    ///
     pub fn values(&mut self, value: &str) -> &mut Self {
-        self.svg.push_str(format!("values=\"{value}\" ").as_str());
+        self.attr("values", value);
         self
     }
+
+    ///
+    /// # Apply a filter on the current element
+    ///
+    /// - `f` The id of a filter, `none`, `inherit`, or a filter value as `url(#id)` or `blur(2px)`
+    ///
     pub fn filter(&mut self, f: &str) -> &mut Self {
-        self.svg.push_str(format!("filter=\"{f}\" ").as_str());
+        self.attr("filter", filter::attribute(f).as_str());
         self
     }
+
+    ///
+    /// # Name the result of the current filter primitive
+    ///
+    /// - `f` The name
+    ///
     pub fn result(&mut self, f: &str) -> &mut Self {
-        self.svg.push_str(format!("filter=\"{f}\" ").as_str());
+        self.attr("result", f);
         self
     }
+
+    ///
+    /// # Define the input of the current filter primitive
+    ///
+    /// - `i` The input
+    ///
     pub fn in1(&mut self, i: &str) -> &mut Self {
-        self.svg.push_str(format!("in=\"{i}\" ").as_str());
+        self.attr("in", i);
         self
     }
 
+    ///
+    /// # Define the second input of the current filter primitive
+    ///
+    /// - `i` The input
+    ///
     pub fn in2(&mut self, i: &str) -> &mut Self {
-        self.svg.push_str(format!("in2=\"{i}\" ").as_str());
+        self.attr("in2", i);
+        self
+    }
+
+    ///
+    /// # Start a filter, the primitives are added with the `fe_` methods
+    ///
+    pub fn filter_element(&mut self) -> &mut Self {
+        self.open("filter");
+        self
+    }
+
+    ///
+    /// # Close a filter
+    ///
+    pub fn close_filter(&mut self) -> &mut Self {
+        self.close_element("filter");
         self
     }
 
     pub fn fe_offset(&mut self) -> &mut Self {
-        self.svg.push_str("<feOffset ");
+        self.open("feOffset");
         self
     }
 
@@ -77,7 +196,22 @@ impl Svg {
     /// # Start the svg
     ///
     pub fn start(&mut self) -> &mut Self {
-        self.svg.push_str(format!("<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" width=\"{}{}\" height=\"{}{}\" viewBox=\"{}\" role=\"img\" id=\"{}\">", self.width,self.width_unit, self.height,self.height_unit,self.view_box,self.id).as_str());
+        let mut svg = Node::new("svg");
+        svg.set_attribute("xmlns", "http://www.w3.org/2000/svg")
+            .set_attribute("xmlns:xlink", "http://www.w3.org/1999/xlink")
+            .set_attribute("width", self.width.to_string().as_str())
+            .set_attribute("height", self.height.to_string().as_str())
+            .set_attribute("viewBox", self.view_box.to_string().as_str());
+        if let Some(aspect) = self.aspect {
+            svg.set_attribute("preserveAspectRatio", aspect.to_string().as_str());
+        }
+        svg.set_attribute("role", "img")
+            .set_attribute("id", self.id.as_str());
+        if self.root.is_some() {
+            return self.open_node(svg);
+        }
+        self.root = Some(svg);
+        self.open = vec![0];
         self
     }
 
@@ -85,86 +219,99 @@ impl Svg {
     /// # Start a circle
     ///
     pub fn circle(&mut self) -> &mut Self {
-        self.svg.push_str("<circle ");
+        self.open("circle");
         self
     }
 
-    fn generate_badge(
+    ///
+    /// # Build a badge
+    ///
+    /// - `fetcher` The source of the icon
+    /// - `t`       The status
+    /// - `label`   The left text
+    /// - `e`       The right text on failure
+    /// - `s`       The right text on success
+    /// - `img`     The icon uri
+    ///
+    #[must_use]
+    pub fn badge_with<F: ResourceFetcher + 'static>(
+        fetcher: F,
         t: bool,
-        img: &str,
         label: &str,
-        s: &str,
         e: &str,
-        dir: &str,
-        f: &str,
-    ) -> i32 {
-        let mut svg = Self::new(164.0, "px", 28.0, "px", "0 0 164 28", label);
-        let mut right_bg = String::new();
-        let mut left_bg = String::new();
-        let mut text = String::new();
-        if t {
-            right_bg.push_str("#00ff00");
-            left_bg.push_str("#222");
-            text.push_str(s);
+        s: &str,
+        img: &str,
+    ) -> Self {
+        let mut svg = Self::new(Length::px(164.0), Length::px(28.0), "0 0 164 28", label);
+        let left_bg = Color::hex(0x0022_2222);
+        let (right_bg, text) = if t {
+            (Color::hex(0x0000_ff00), s)
         } else {
-            right_bg.push_str("#ff0000");
-            left_bg.push_str("#222");
-            text.push_str(e);
-        }
-        svg.start()
+            (Color::hex(0x00ff_0000), e)
+        };
+        svg.fetcher(fetcher)
+            .start()
             .g()
             .shape_rendering("crispEdges")
             .close_tag()
             .rect()
-            .width(82.05, "px")
-            .height(28.0, "px")
-            .fill(left_bg.as_str())
+            .width(Length::px(82.05))
+            .height(Length::px(28.0))
+            .fill(left_bg)
             .close_tag()
             .close_rect()
             .image(img)
-            .x(9.0, "px")
-            .y(7.0, "px")
-            .width(14.0, "px")
-            .height(14.0, "px")
+            .x(Length::px(9.0))
+            .y(Length::px(7.0))
+            .width(Length::px(14.0))
+            .height(Length::px(14.0))
             .close()
             .close_group()
             .group()
             .rect()
-            .x(82.05, "px")
-            .width(82.1716, "px")
-            .height(100.0, "px")
-            .fill(right_bg.as_str())
+            .x(Length::px(82.05))
+            .width(Length::px(82.1716))
+            .height(Length::px(100.0))
+            .fill(right_bg)
             .close_tag()
             .close_rect()
             .text_anchor("middle")
             .text_rendering("geometricPrecision")
-            .font_size(100.0, "px")
+            .font_size(Length::px(100.0))
             .close_tag()
             .text()
-            .x(495.26276, "px")
-            .y(175.0, "px")
+            .x(Length::px(495.26276))
+            .y(Length::px(175.0))
             .transform("scale(0.1)")
             .fill("#fff")
-            .text_length(410.5255, "px")
+            .text_length(Length::px(410.5255))
             .close_tag()
             .content(label)
             .close_text()
             .text()
-            .x(1231.3833, "px")
-            .y(175.0, "px")
+            .x(Length::px(1231.3833))
+            .y(Length::px(175.0))
             .transform("scale(0.1)")
             .font_weight("bold")
             .fill("#fff")
-            .text_length(581.71564, "px")
+            .text_length(Length::px(581.71564))
             .close_tag()
-            .content(text.as_str())
+            .content(text)
             .close_text()
             .close_group()
-            .end()
-            .save(dir, f)
+            .end();
+        svg
     }
 
-    #[must_use]
+    ///
+    /// # Generate a badge
+    ///
+    /// The icon is resolved with the default `OfflineFetcher`.
+    ///
+    /// # Errors
+    ///
+    /// On the save failure
+    ///
     pub fn badge(
         t: bool,
         l: &str,
@@ -173,81 +320,98 @@ impl Svg {
         url: &str,
         output_dir: &str,
         filename: &str,
-    ) -> i32 {
-        Self::generate_badge(t, url, l, s, e, output_dir, filename)
+    ) -> Result<PathBuf, SvgError> {
+        Self::badge_with(OfflineFetcher::default(), t, l, e, s, url).save(output_dir, filename)
     }
 
-    fn text_length(&mut self, length: f64, unit: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("textLength=\"{length}{unit}\"").as_str());
+    fn text_length<L: Into<Length>>(&mut self, length: L) -> &mut Self {
+        self.attr("textLength", length.into().to_string().as_str());
         self
     }
     fn font_weight(&mut self, weight: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("font-weight=\"{weight}\" ").as_str());
+        self.attr("font-weight", weight);
         self
     }
     fn text_rendering(&mut self, rendering: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("text-rendering=\"{rendering}\" ").as_str());
+        self.attr("text-rendering", rendering);
         self
     }
 
     fn shape_rendering(&mut self, rendering: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("shape-rendering=\"{rendering}\" ").as_str());
+        self.attr("shape-rendering", rendering);
+        self
+    }
+
+    ///
+    /// # Define the source of the images
+    ///
+    /// - `fetcher` The fetcher used by `image`
+    ///
+    pub fn fetcher<F: ResourceFetcher + 'static>(&mut self, fetcher: F) -> &mut Self {
+        self.fetcher = Arc::new(fetcher);
         self
     }
 
     ///
-    /// # Start an image
+    /// # Define the serialization mode
     ///
-    /// # Panics
+    /// - `format` The mode, compact by default
     ///
-    /// if base64 not work and wget not found
+    pub fn format(&mut self, format: Format) -> &mut Self {
+        self.format = format;
+        self
+    }
+
+    ///
+    /// # Start an image resolved by the fetcher
+    ///
+    /// - `uri` The image uri
     ///
     pub fn image(&mut self, uri: &str) -> &mut Self {
-        let img = uri.split('/').last().expect("");
-        let base = File::create("b.txt").expect("");
-        if Path::new(format!("./{img}").as_str()).is_file() {
-            fs::remove_file(format!("./{img}").as_str()).expect("");
-        }
-        assert!(Command::new("wget")
-            .arg("-q")
-            .arg(uri)
-            .current_dir(".")
-            .spawn()
-            .expect("")
-            .wait()
-            .expect("")
-            .success());
-        assert!(Command::new("base64")
-            .arg(img)
-            .stdout(base)
-            .current_dir(".")
-            .spawn()
-            .expect("")
-            .wait()
-            .expect("")
-            .success());
-        self.svg.push_str(
-            format!(
-                "<image xlink:href=\"data:image/svg+xml;base64,{}\" ",
-                fs::read_to_string("b.txt").expect("a")
-            )
-            .as_str(),
-        );
-        if Path::new(format!("./{img}").as_str()).is_file() {
-            fs::remove_file(format!("./{img}").as_str()).expect("");
+        match self.fetcher.fetch(uri) {
+            Ok(data) => self.image_from_bytes(data.as_slice()),
+            Err(e) => {
+                self.fail(e);
+                self.open("image")
+            }
+        }
+    }
+
+    ///
+    /// # Start an image embedding a local file
+    ///
+    /// - `path` The image file
+    ///
+    pub fn image_from_path<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        match fs::read(path) {
+            Ok(data) => self.image_from_bytes(data.as_slice()),
+            Err(e) => {
+                self.fail(SvgError::Io(e));
+                self.open("image")
+            }
+        }
+    }
+
+    ///
+    /// # Start an image embedding the data
+    ///
+    /// The format is detected from the magic bytes.
+    ///
+    /// - `data` The png, jpeg, gif, webp or svg content
+    ///
+    pub fn image_from_bytes(&mut self, data: &[u8]) -> &mut Self {
+        self.open("image");
+        match data_uri(data) {
+            Some(uri) => self.attr("xlink:href", uri.as_str()),
+            None => self.fail(SvgError::UnsupportedImage),
         }
-        self
     }
 
     ///
     /// # Close a circle
     ///
     pub fn close_circle(&mut self) -> &mut Self {
-        self.svg.push_str("</circle> ");
+        self.close_element("circle");
         self
     }
 
@@ -255,7 +419,7 @@ impl Svg {
     /// # Close the opened svg tag
     ///
     pub fn close(&mut self) -> &mut Self {
-        self.svg.push_str("/>");
+        self.open.pop();
         self
     }
 
@@ -263,7 +427,7 @@ impl Svg {
     /// # Start a group
     ///
     pub fn g(&mut self) -> &mut Self {
-        self.svg.push_str("<g ");
+        self.open("g");
         self
     }
 
@@ -271,7 +435,7 @@ impl Svg {
     /// # Create a group
     ///
     pub fn group(&mut self) -> &mut Self {
-        self.svg.push_str("<g>");
+        self.open("g");
         self
     }
 
@@ -281,7 +445,7 @@ impl Svg {
     /// - `target` The target
     ///
     pub fn target(&mut self, target: &str) -> &mut Self {
-        self.svg.push_str(format!("target=\"{target}\" ").as_str());
+        self.attr("target", target);
         self
     }
 
@@ -291,8 +455,7 @@ impl Svg {
     /// - `family` The font
     ///
     pub fn font_family(&mut self, family: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("font-family=\"{family}\" ").as_str());
+        self.attr("font-family", family);
         self
     }
 
@@ -300,11 +463,9 @@ impl Svg {
     /// # Add a font size
     ///
     /// - `size` The font size
-    /// - `unit` The font unity
     ///
-    pub fn font_size(&mut self, size: f64, unit: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("font-size=\"{size}{unit}\" ").as_str());
+    pub fn font_size<L: Into<Length>>(&mut self, size: L) -> &mut Self {
+        self.attr("font-size", size.into().to_string().as_str());
         self
     }
 
@@ -312,7 +473,7 @@ impl Svg {
     /// # Start a link
     ///
     pub fn a(&mut self) -> &mut Self {
-        self.svg.push_str("<a ");
+        self.open("a");
         self
     }
 
@@ -320,7 +481,7 @@ impl Svg {
     /// # Close a link
     ///
     pub fn close_a(&mut self) -> &mut Self {
-        self.svg.push_str("</a> ");
+        self.close_element("a");
         self
     }
 
@@ -328,7 +489,7 @@ impl Svg {
     /// # Start a text
     ///
     pub fn text(&mut self) -> &mut Self {
-        self.svg.push_str("<text ");
+        self.open("text");
         self
     }
 
@@ -336,7 +497,7 @@ impl Svg {
     /// # Start an ellipse
     ///
     pub fn ellipse(&mut self) -> &mut Self {
-        self.svg.push_str("<ellipse ");
+        self.open("ellipse");
         self
     }
 
@@ -344,7 +505,7 @@ impl Svg {
     /// # Close a text
     ///
     pub fn close_text(&mut self) -> &mut Self {
-        self.svg.push_str("</text> ");
+        self.close_element("text");
         self
     }
 
@@ -354,7 +515,7 @@ impl Svg {
     /// - `class` The class name
     ///
     pub fn class(&mut self, class: &str) -> &mut Self {
-        self.svg.push_str(format!("class=\"{class}\" ").as_str());
+        self.attr("class", class);
         self
     }
     ///
@@ -363,8 +524,7 @@ impl Svg {
     /// - `limit` The limit
     ///
     pub fn stroke_miterlimit(&mut self, limit: f64) -> &mut Self {
-        self.svg
-            .push_str(format!("stroke-miterlimit=\"{limit}\" ").as_str());
+        self.attr("stroke-miterlimit", limit.to_string().as_str());
         self
     }
 
@@ -374,8 +534,7 @@ impl Svg {
     /// - `event` The event
     ///
     pub fn pointer_events(&mut self, event: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("pointer-events=\"{event}\" ").as_str());
+        self.attr("pointer-events", event);
         self
     }
 
@@ -385,29 +544,28 @@ impl Svg {
     /// - `lang` The lang event
     ///
     pub fn system_language(&mut self, lang: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("systemLanguage=\"{lang}\" ").as_str());
+        self.attr("systemLanguage", lang);
         self
     }
 
     pub fn div(&mut self) -> &mut Self {
-        self.svg
-            .push_str("<div xmlns=\"http://www.w3.org/1999/xhtml\"  ");
+        self.open("div");
+        self.attr("xmlns", "http://www.w3.org/1999/xhtml");
         self
     }
 
     pub fn close_div(&mut self) -> &mut Self {
-        self.svg.push_str("</div>");
+        self.close_element("div");
         self
     }
 
     pub fn close_foreign_object(&mut self) -> &mut Self {
-        self.svg.push_str("</foreignObject>");
+        self.close_element("foreignObject");
         self
     }
 
     pub fn foreign_object(&mut self) -> &mut Self {
-        self.svg.push_str("<foreignObject ");
+        self.open("foreignObject");
         self
     }
 
@@ -417,8 +575,9 @@ impl Svg {
     /// - `css` The class name
     ///
     pub fn css(&mut self, css: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("<style text=\"text/css\">{css}</style>").as_str());
+        let mut style = Node::new("style");
+        style.set_attribute("text", "text/css").append_text(css);
+        self.append(style);
         self
     }
 
@@ -428,7 +587,7 @@ impl Svg {
     /// - `t` The type name
     ///
     pub fn t(&mut self, t: &str) -> &mut Self {
-        self.svg.push_str(format!("type=\"{t} ").as_str());
+        self.attr("type", t);
         self
     }
 
@@ -436,7 +595,6 @@ impl Svg {
     /// # Close a tag
     ///
     pub fn close_tag(&mut self) -> &mut Self {
-        self.svg.push_str("> ");
         self
     }
 
@@ -444,7 +602,7 @@ impl Svg {
     /// # Start a tspan
     ///
     pub fn tspan(&mut self) -> &mut Self {
-        self.svg.push_str("<tspan ");
+        self.open("tspan");
         self
     }
 
@@ -452,7 +610,7 @@ impl Svg {
     /// # Close a tspan
     ///
     pub fn close_tspan(&mut self) -> &mut Self {
-        self.svg.push_str("</tspan> ");
+        self.close_element("tspan");
         self
     }
 
@@ -460,7 +618,7 @@ impl Svg {
     /// # Create a feMorphology
     ///
     pub fn fe_morphology(&mut self) -> &mut Self {
-        self.svg.push_str("<feMorphology ");
+        self.open("feMorphology");
         self
     }
 
@@ -468,7 +626,7 @@ impl Svg {
     /// # Create a feConvolveMatrix
     ///
     pub fn fe_convolve_matrix(&mut self) -> &mut Self {
-        self.svg.push_str("<feConvolveMatrix ");
+        self.open("feConvolveMatrix");
         self
     }
 
@@ -476,7 +634,7 @@ impl Svg {
     /// # Create a feBlend
     ///
     pub fn fe_blend(&mut self) -> &mut Self {
-        self.svg.push_str("<feBlend ");
+        self.open("feBlend");
         self
     }
 
@@ -484,7 +642,7 @@ impl Svg {
     /// # Create a feColorMatrix
     ///
     pub fn fe_color_matrix(&mut self) -> &mut Self {
-        self.svg.push_str("<feColorMatrix ");
+        self.open("feColorMatrix");
         self
     }
 
@@ -492,7 +650,7 @@ impl Svg {
     /// # Start a span
     ///
     pub fn span(&mut self) -> &mut Self {
-        self.svg.push_str("<span ");
+        self.open("span");
         self
     }
 
@@ -500,7 +658,9 @@ impl Svg {
     /// # Start a span
     ///
     pub fn b(&mut self, text: &str) -> &mut Self {
-        self.svg.push_str(format!("<b>{text}</b>").as_str());
+        let mut b = Node::new("b");
+        b.append_text(text);
+        self.append(b);
         self
     }
 
@@ -508,7 +668,7 @@ impl Svg {
     /// # Close a span
     ///
     pub fn close_span(&mut self) -> &mut Self {
-        self.svg.push_str("</span> ");
+        self.close_element("span");
         self
     }
 
@@ -516,7 +676,7 @@ impl Svg {
     /// # Create a switch
     ///
     pub fn switch(&mut self) -> &mut Self {
-        self.svg.push_str("<switch> ");
+        self.open("switch");
         self
     }
 
@@ -524,7 +684,7 @@ impl Svg {
     /// # Close a switch
     ///
     pub fn close_switch(&mut self) -> &mut Self {
-        self.svg.push_str("</switch> ");
+        self.close_element("switch");
         self
     }
 
@@ -532,7 +692,7 @@ impl Svg {
     /// # Close a rect
     ///
     pub fn close_rect(&mut self) -> &mut Self {
-        self.svg.push_str("</rect> ");
+        self.close_element("rect");
         self
     }
 
@@ -542,7 +702,7 @@ impl Svg {
     /// - `duration`    The duration
     ///
     pub fn dur(&mut self, duration: &str) -> &mut Self {
-        self.svg.push_str(format!("dur=\"{duration}\" ").as_str());
+        self.attr("dur", duration);
         self
     }
 
@@ -552,7 +712,7 @@ impl Svg {
     /// - `duration`    The max duration
     ///
     pub fn max(&mut self, duration: &str) -> &mut Self {
-        self.svg.push_str(format!("max=\"{duration}\" ").as_str());
+        self.attr("max", duration);
         self
     }
     ///
@@ -561,7 +721,7 @@ impl Svg {
     /// - `duration` The beginning value
     ///
     pub fn begin(&mut self, duration: &str) -> &mut Self {
-        self.svg.push_str(format!("begin=\"{duration}\" ").as_str());
+        self.attr("begin", duration);
         self
     }
 
@@ -571,8 +731,7 @@ impl Svg {
     /// - `restart` The restart policy value
     ///
     pub fn restart(&mut self, restart: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("restart=\"{restart}\" ").as_str());
+        self.attr("restart", restart);
         self
     }
     ///
@@ -581,7 +740,7 @@ impl Svg {
     /// - `to` The max value
     ///
     pub fn to(&mut self, to: &str) -> &mut Self {
-        self.svg.push_str(format!("to=\"{to}\" ").as_str());
+        self.attr("to", to);
         self
     }
 
@@ -591,7 +750,7 @@ impl Svg {
     /// - `to` The max value
     ///
     pub fn key_times(&mut self, key: &str) -> &mut Self {
-        self.svg.push_str(format!("keyTimes=\"{key}\" ").as_str());
+        self.attr("keyTimes", key);
         self
     }
 
@@ -601,19 +760,42 @@ impl Svg {
     /// - `name` The name
     ///
     pub fn attribute_name(&mut self, name: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("attributeName=\"{name}\" ").as_str());
+        self.attr("attributeName", name);
         self
     }
 
     ///
     /// # Set the attribute content
     ///
+    /// The xml special characters are escaped, use `raw` to insert a markup.
+    ///
     /// - `data` The value
     ///
     pub fn content(&mut self, data: &str) -> &mut Self {
-        self.svg.push_str(data);
-        self
+        match self.current() {
+            Some(node) => {
+                node.append_text(data);
+                self
+            }
+            None => self.fail(SvgError::NotStarted("text".to_string())),
+        }
+    }
+
+    ///
+    /// # Insert a markup without escaping
+    ///
+    /// The markup is written as is, it must be well-formed.
+    ///
+    /// - `markup` The markup to insert
+    ///
+    pub fn raw(&mut self, markup: &str) -> &mut Self {
+        match self.current() {
+            Some(node) => {
+                node.append_raw(markup);
+                self
+            }
+            None => self.fail(SvgError::NotStarted("markup".to_string())),
+        }
     }
 
     ///
@@ -622,8 +804,7 @@ impl Svg {
     /// - `anchor` The position
     ///
     pub fn text_anchor(&mut self, anchor: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("text-anchor=\"{anchor}\" ").as_str());
+        self.attr("text-anchor", anchor);
         self
     }
 
@@ -633,8 +814,7 @@ impl Svg {
     /// - `align` The position
     ///
     pub fn alignment_baseline(&mut self, align: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("alignment-baseline=\"{align}\" ").as_str());
+        self.attr("alignment-baseline", align);
         self
     }
 
@@ -644,7 +824,7 @@ impl Svg {
     /// - `d` The new position
     ///
     pub fn dx(&mut self, d: &str) -> &mut Self {
-        self.svg.push_str(format!("dx=\"{d}\" ").as_str());
+        self.attr("dx", d);
         self
     }
 
@@ -654,7 +834,7 @@ impl Svg {
     /// - `d` The new position
     ///
     pub fn dy(&mut self, d: &str) -> &mut Self {
-        self.svg.push_str(format!("dy=\"{d}\" ").as_str());
+        self.attr("dy", d);
         self
     }
 
@@ -664,8 +844,7 @@ impl Svg {
     /// - `t` The type
     ///
     pub fn attribute_type(&mut self, t: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("attributeType=\"{t}\" ").as_str());
+        self.attr("attributeType", t);
         self
     }
 
@@ -675,7 +854,7 @@ impl Svg {
     /// - `t`   The value
     ///
     pub fn repeat_count(&mut self, t: &str) -> &mut Self {
-        self.svg.push_str(format!("repeatCount=\"{t}\" ").as_str());
+        self.attr("repeatCount", t);
         self
     }
 
@@ -685,7 +864,7 @@ impl Svg {
     /// - `from` The provenance value
     ///
     pub fn from(&mut self, from: &str) -> &mut Self {
-        self.svg.push_str(format!("from=\"{from}\" ").as_str());
+        self.attr("from", from);
         self
     }
 
@@ -693,28 +872,31 @@ impl Svg {
     /// # Close a group
     ///
     pub fn close_group(&mut self) -> &mut Self {
-        self.svg.push_str("</g> ");
+        self.close_element("g");
         self
     }
 
     pub fn defs(&mut self) -> &mut Self {
-        self.svg.push_str("<defs> ");
+        self.open("defs");
         self
     }
 
     pub fn close_deps(&mut self) -> &mut Self {
-        self.svg.push_str("</defs> ");
+        self.close_element("defs");
         self
     }
 
     ///
     /// # Add a transformation
     ///
-    /// - `transform` The transformation
+    /// - `transform` The transformation, a string or a `Transform`, reported on save when invalid
     ///
-    pub fn transform(&mut self, transform: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("transform=\"{transform}\" ").as_str());
+    pub fn transform<T: Display>(&mut self, transform: T) -> &mut Self {
+        let transform = transform.to_string();
+        if let Err(e) = transform.parse::<Transform>() {
+            self.fail(e);
+        }
+        self.attr("transform", transform.as_str());
         self
     }
     ///
@@ -723,7 +905,7 @@ impl Svg {
     /// - `l` The path length
     ///
     pub fn path_length(&mut self, l: &str) -> &mut Self {
-        self.svg.push_str(format!("pathLength=\"{l}\" ").as_str());
+        self.attr("pathLength", l);
         self
     }
 
@@ -731,31 +913,36 @@ impl Svg {
     /// # Define a width
     ///
     /// - `width`   The width
-    /// - `unit`    The width unit
     ///
-    pub fn width(&mut self, width: f64, unit: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("width=\"{width}{unit}\" ").as_str());
+    pub fn width<L: Into<Length>>(&mut self, width: L) -> &mut Self {
+        self.attr("width", width.into().to_string().as_str());
         self
     }
 
     ///
     /// # Set a fill color
     ///
-    /// - `fill` The color
+    /// - `fill` The paint, a string is parsed and reported on save when invalid
     ///
-    pub fn fill(&mut self, fill: &str) -> &mut Self {
-        self.svg.push_str(format!("fill=\"{fill}\" ").as_str());
-        self
+    pub fn fill<P>(&mut self, fill: P) -> &mut Self
+    where
+        P: TryInto<Paint>,
+        SvgError: From<P::Error>,
+    {
+        self.paint("fill", fill.try_into().map_err(SvgError::from))
     }
 
     ///
     /// # Add a data
     ///
-    /// - `d` The data
+    /// - `d` The data, a string or a `PathBuilder`, reported on save when invalid
     ///
-    pub fn d(&mut self, d: &str) -> &mut Self {
-        self.svg.push_str(format!("d=\"{d}\" ").as_str());
+    pub fn d<D: Display>(&mut self, d: D) -> &mut Self {
+        let d = d.to_string();
+        if let Err(e) = parse(d.as_str()) {
+            self.fail(e);
+        }
+        self.attr("d", d.as_str());
         self
     }
 
@@ -763,20 +950,18 @@ impl Svg {
     /// # Define  attribute defines a radius on the x-axis
     ///
     /// - `rx`   radius on the x-axis
-    /// - `unit` radius on the x-axis unit
     ///
-    pub fn rx(&mut self, rx: f64, unit: &str) -> &mut Self {
-        self.svg.push_str(format!("rx=\"{rx}{unit}\" ").as_str());
+    pub fn rx<L: Into<Length>>(&mut self, rx: L) -> &mut Self {
+        self.attr("rx", rx.into().to_string().as_str());
         self
     }
     ///
     /// # Define  attribute defines a radius on the y-axis
     ///
     /// - `ry` radius on the y-axis
-    /// - `unit` radius on the y-axis unit
     ///
-    pub fn ry(&mut self, ry: f64, unit: &str) -> &mut Self {
-        self.svg.push_str(format!("ry=\"{ry}{unit}\" ").as_str());
+    pub fn ry<L: Into<Length>>(&mut self, ry: L) -> &mut Self {
+        self.attr("ry", ry.into().to_string().as_str());
         self
     }
 
@@ -787,25 +972,28 @@ impl Svg {
     /// - `y1`      The first y-coordinate
     /// - `x2`      The second x-coordinate
     /// - `y2`      The second y-coordinate
-    /// - `stroke`  The line color
+    /// - `stroke`  The line paint, an invalid color is reported by `save`
     ///
-    pub fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, stroke: &str) -> &mut Self {
-        self.svg.push_str(
-            format!("<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"{stroke}\" ")
-                .as_str(),
-        );
-
-        self
+    pub fn line<P>(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, stroke: P) -> &mut Self
+    where
+        P: TryInto<Paint>,
+        SvgError: From<P::Error>,
+    {
+        self.open("line");
+        self.attr("x1", x1.to_string().as_str());
+        self.attr("y1", y1.to_string().as_str());
+        self.attr("x2", x2.to_string().as_str());
+        self.attr("y2", y2.to_string().as_str());
+        self.stroke(stroke)
     }
 
     ///
     /// # define a rayon
     ///
     /// - `r`       The rayon
-    /// - `unit`    The rayon unit
     ///
-    pub fn r(&mut self, r: f64, unit: &str) -> &mut Self {
-        self.svg.push_str(format!("r=\"{r}{unit}\" ").as_str());
+    pub fn r<L: Into<Length>>(&mut self, r: L) -> &mut Self {
+        self.attr("r", r.into().to_string().as_str());
         self
     }
 
@@ -813,10 +1001,9 @@ impl Svg {
     /// # Defines the x-axis coordinate for the center point of an element.
     ///
     /// - `cx`      The x-axis coordinate
-    /// - `unit`    The x-axis coordinate unit
     ///
-    pub fn cx(&mut self, cx: f64, unit: &str) -> &mut Self {
-        self.svg.push_str(format!("cx=\"{cx}{unit}\" ").as_str());
+    pub fn cx<L: Into<Length>>(&mut self, cx: L) -> &mut Self {
+        self.attr("cx", cx.into().to_string().as_str());
         self
     }
 
@@ -824,44 +1011,87 @@ impl Svg {
     /// # defines the y-axis coordinate for the center point of an element.
     ///
     /// - `cy`      The y-axis coordinate
-    /// - `unit`    The y-axis coordinate unit
     ///
-    pub fn cy(&mut self, cy: f64, unit: &str) -> &mut Self {
-        self.svg.push_str(format!("cy=\"{cy}{unit}\" ").as_str());
+    pub fn cy<L: Into<Length>>(&mut self, cy: L) -> &mut Self {
+        self.attr("cy", cy.into().to_string().as_str());
         self
     }
 
     ///
     /// # define a stroke
     ///
-    /// - `stoke` the stoke color
+    /// - `stroke` The paint, a string is parsed and reported on save when invalid
     ///
-    pub fn stroke(&mut self, stroke: &str) -> &mut Self {
-        self.svg.push_str(format!("stroke=\"{stroke}\" ").as_str());
-        self
+    pub fn stroke<P>(&mut self, stroke: P) -> &mut Self
+    where
+        P: TryInto<Paint>,
+        SvgError: From<P::Error>,
+    {
+        self.paint("stroke", stroke.try_into().map_err(SvgError::from))
     }
 
     ///
     /// # Define a stoke width
     ///
     /// - `width`   The stoke width
-    /// - `unit`    The stoke width unit
     ///
-    pub fn stroke_width(&mut self, width: f64, unit: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("stroke-width=\"{width}{unit}\" ").as_str());
+    pub fn stroke_width<L: Into<Length>>(&mut self, width: L) -> &mut Self {
+        self.attr("stroke-width", width.into().to_string().as_str());
         self
     }
 
+    ///
+    /// # Place a marker on the first vertex
+    ///
+    /// - `id` The id of the marker
+    ///
+    pub fn marker_start(&mut self, id: &str) -> &mut Self {
+        self.reference("marker-start", id)
+    }
+
+    ///
+    /// # Place a marker on the vertices between the first and the last
+    ///
+    /// - `id` The id of the marker
+    ///
+    pub fn marker_mid(&mut self, id: &str) -> &mut Self {
+        self.reference("marker-mid", id)
+    }
+
+    ///
+    /// # Place a marker on the last vertex
+    ///
+    /// - `id` The id of the marker
+    ///
+    pub fn marker_end(&mut self, id: &str) -> &mut Self {
+        self.reference("marker-end", id)
+    }
+
+    ///
+    /// # Draw only the parts of the current element inside a clip path
+    ///
+    /// - `id` The id of the clip path
+    ///
+    pub fn clip_path(&mut self, id: &str) -> &mut Self {
+        self.reference("clip-path", id)
+    }
+
+    ///
+    /// # Apply a mask on the current element
+    ///
+    /// - `id` The id of the mask
+    ///
+    pub fn mask(&mut self, id: &str) -> &mut Self {
+        self.reference("mask", id)
+    }
+
     ///
     /// # Define a height
     ///
     /// - `height`  The height
-    /// - `unit`    The height unit
     ///
-    pub fn height(&mut self, height: f64, unit: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("height=\"{height}{unit}\" ").as_str());
+    pub fn height<L: Into<Length>>(&mut self, height: L) -> &mut Self {
+        self.attr("height", height.into().to_string().as_str());
         self
     }
 
@@ -871,7 +1101,7 @@ impl Svg {
     /// - `id` The id name
     ///
     pub fn id(&mut self, id: &str) -> &mut Self {
-        self.svg.push_str(format!("id=\"{id}\" ").as_str());
+        self.attr("id", id);
         self
     }
 
@@ -879,10 +1109,9 @@ impl Svg {
     /// # Pos in x
     ///
     /// - `x`       The position in x
-    /// - `unit`    The position unit
     ///
-    pub fn x(&mut self, x: f64, unit: &str) -> &mut Self {
-        self.svg.push_str(format!("x=\"{x}{unit}\" ").as_str());
+    pub fn x<L: Into<Length>>(&mut self, x: L) -> &mut Self {
+        self.attr("x", x.into().to_string().as_str());
         self
     }
 
@@ -890,10 +1119,9 @@ impl Svg {
     /// # Pos in y
     ///
     /// - `y`       The position in y
-    /// - `unit`    The position unit
     ///
-    pub fn y(&mut self, y: f64, unit: &str) -> &mut Self {
-        self.svg.push_str(format!("y=\"{y}{unit}\" ").as_str());
+    pub fn y<L: Into<Length>>(&mut self, y: L) -> &mut Self {
+        self.attr("y", y.into().to_string().as_str());
         self
     }
 
@@ -903,7 +1131,7 @@ impl Svg {
     /// - `href` the href
     ///
     pub fn href(&mut self, href: &str) -> &mut Self {
-        self.svg.push_str(format!("href=\"{href}\" ").as_str());
+        self.attr("href", href);
         self
     }
 
@@ -913,8 +1141,7 @@ impl Svg {
     /// - `href` the href
     ///
     pub fn link(&mut self, href: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("xlink:href=\"{href}\" ").as_str());
+        self.attr("xlink:href", href);
         self
     }
 
@@ -924,8 +1151,9 @@ impl Svg {
     /// - `title` the title
     ///
     pub fn title(&mut self, title: &str) -> &mut Self {
-        self.svg
-            .push_str(format!("<title>{title}</title>").as_str());
+        let mut t = Node::new("title");
+        t.append_text(title);
+        self.append(t);
         self
     }
 
@@ -933,7 +1161,7 @@ impl Svg {
     /// # Start a use
     ///
     pub fn u(&mut self) -> &mut Self {
-        self.svg.push_str("<use ");
+        self.open("use");
         self
     }
 
@@ -941,7 +1169,7 @@ impl Svg {
     /// # Start a path
     ///
     pub fn path(&mut self) -> &mut Self {
-        self.svg.push_str("<path ");
+        self.open("path");
         self
     }
 
@@ -951,7 +1179,7 @@ impl Svg {
     /// - `style`   The style to add
     ///
     pub fn style(&mut self, style: &str) -> &mut Self {
-        self.svg.push_str(format!("style=\"{style}\" ").as_str());
+        self.attr("style", style);
         self
     }
 
@@ -959,7 +1187,7 @@ impl Svg {
     /// # Start a rectangle
     ///
     pub fn rect(&mut self) -> &mut Self {
-        self.svg.push_str("<rect  ");
+        self.open("rect");
         self
     }
 
@@ -967,48 +1195,651 @@ impl Svg {
     /// # Close the svg
     ///
     pub fn end(&mut self) -> &mut Self {
-        self.svg.push_str("</svg>");
+        self.close_element("svg");
         self
     }
 
     ///
-    /// # Write the svg to disk
+    /// # Build elements with the typed api
+    ///
+    /// The elements are appended to the element currently open.
+    ///
+    pub fn build(&mut self) -> Document<'_> {
+        Document::new(self)
+    }
+
     ///
-    /// # Panics
+    /// # The svg element
+    ///
+    /// None before the call of `start`
+    ///
+    #[must_use]
+    pub fn root(&self) -> Option<&Node> {
+        self.root.as_ref()
+    }
+
     ///
-    /// On svg creation failure
+    /// # The mutable svg element
+    ///
+    /// None before the call of `start`
+    ///
+    pub fn root_mut(&mut self) -> Option<&mut Node> {
+        self.root.as_mut()
+    }
+
+    ///
+    /// # Register a definition in the defs of the document
+    ///
+    /// A definition with the same id is replaced. The inherited definition
+    /// must be registered first and the id must not be used by an element of
+    /// the document, otherwise the error is reported on save.
+    ///
+    /// - `definition` The gradient, pattern, marker, clip path, mask or filter
+    ///
+    /// # Return
+    ///
+    /// The id of the definition
+    ///
+    pub fn define<D: Definition>(&mut self, mut definition: D) -> String {
+        let id = definition.id().to_string();
+        if let Some(error) = definition.take_error() {
+            self.fail(error);
+        }
+        if self
+            .root
+            .as_ref()
+            .is_some_and(|r| r.find(id.as_str()).is_some())
+        {
+            self.fail(SvgError::DuplicateId(id.clone()));
+        }
+        if let Some(inherited) = definition.inherits() {
+            if !self
+                .defs
+                .iter()
+                .any(|d| d.attribute("id") == Some(inherited))
+            {
+                self.fail(SvgError::ResourceNotFound(format!("#{inherited}")));
+            }
+        }
+        let node = definition.to_node();
+        match self
+            .defs
+            .iter_mut()
+            .find(|d| d.attribute("id") == Some(id.as_str()))
+        {
+            Some(existing) => *existing = node,
+            None => self.defs.push(node),
+        }
+        id
+    }
+
+    ///
+    /// # The registered definitions
+    ///
+    #[must_use]
+    pub fn definitions(&self) -> &[Node] {
+        &self.defs
+    }
+
+    ///
+    /// # Apply a drop shadow on the current element
+    ///
+    /// - `dx`      The horizontal shift
+    /// - `dy`      The vertical shift
+    /// - `blur`    The amount of blur
+    /// - `color`   The color of the shadow
+    ///
+    pub fn drop_shadow(&mut self, dx: f64, dy: f64, blur: f64, color: Color) -> &mut Self {
+        self.effect("drop-shadow", |id| {
+            effect::drop_shadow(id, dx, dy, blur, color)
+        })
+    }
+
+    ///
+    /// # Apply a glow on the current element
+    ///
+    /// - `radius`  The size of the halo
+    /// - `color`   The color of the halo
+    ///
+    pub fn outer_glow(&mut self, radius: f64, color: Color) -> &mut Self {
+        self.effect("outer-glow", |id| effect::outer_glow(id, radius, color))
+    }
+
+    ///
+    /// # Apply an inner shadow on the current element
+    ///
+    /// - `dx`      The horizontal shift
+    /// - `dy`      The vertical shift
+    /// - `blur`    The amount of blur
+    /// - `color`   The color of the shadow
+    ///
+    pub fn inner_shadow(&mut self, dx: f64, dy: f64, blur: f64, color: Color) -> &mut Self {
+        self.effect("inner-shadow", |id| {
+            effect::inner_shadow(id, dx, dy, blur, color)
+        })
+    }
+
+    ///
+    /// # Blur the current element
+    ///
+    /// - `radius` The amount of blur
+    ///
+    pub fn blur(&mut self, radius: f64) -> &mut Self {
+        self.effect("blur", |id| effect::blur(id, radius))
+    }
+
+    ///
+    /// # Draw the current element in shades of gray
+    ///
+    pub fn grayscale(&mut self) -> &mut Self {
+        self.effect("grayscale", effect::grayscale)
+    }
+
+    ///
+    /// # Draw the current element in brown tones
+    ///
+    pub fn sepia(&mut self) -> &mut Self {
+        self.effect("sepia", effect::sepia)
+    }
+
+    ///
+    /// # Draw the current element with two colors
+    ///
+    /// - `dark`    The color of the shadows
+    /// - `light`   The color of the highlights
+    ///
+    pub fn duotone(&mut self, dark: Color, light: Color) -> &mut Self {
+        self.effect("duotone", |id| effect::duotone(id, dark, light))
+    }
+
+    ///
+    /// # Draw the current element in relief
+    ///
+    pub fn emboss(&mut self) -> &mut Self {
+        self.effect("emboss", effect::emboss)
+    }
+
+    ///
+    /// # Register a preset filter and apply it on the current element
+    ///
+    /// An identical filter already registered is reused, otherwise the
+    /// filter is named `name`, `name-2`, `name-3`... with the first id used
+    /// neither by a definition nor by an element of the document.
+    ///
+    /// - `name`    The base of the id
+    /// - `preset`  The filter built with an id
+    ///
+    fn effect<F: Fn(&str) -> Filter>(&mut self, name: &str, preset: F) -> &mut Self {
+        let node = preset(name).to_node();
+        let same = |d: &&Node| {
+            let mut d = (*d).clone();
+            d.set_attribute("id", name);
+            d == node
+        };
+        let id = match self.defs.iter().find(same).and_then(|d| d.attribute("id")) {
+            Some(id) => id.to_string(),
+            None => {
+                let id = (1..)
+                    .map(|n| match n {
+                        1 => name.to_string(),
+                        n => format!("{name}-{n}"),
+                    })
+                    .find(|id| {
+                        self.defs.iter().all(|d| d.attribute("id") != Some(id))
+                            && self.root.as_ref().is_none_or(|r| r.find(id).is_none())
+                    })
+                    .unwrap_or_default();
+                self.define(preset(id.as_str()))
+            }
+        };
+        self.reference("filter", id.as_str())
+    }
+
+    ///
+    /// # The view box
+    ///
+    #[must_use]
+    pub const fn view_box(&self) -> ViewBox {
+        self.view_box
+    }
+
+    ///
+    /// # Define how the view box is fitted in the viewport
+    ///
+    /// - `aspect` The alignment and the scaling
+    ///
+    pub fn preserve_aspect_ratio(&mut self, aspect: PreserveAspectRatio) -> &mut Self {
+        self.aspect = Some(aspect);
+        if let Some(root) = self.root.as_mut() {
+            root.set_attribute("preserveAspectRatio", aspect.to_string().as_str());
+        }
+        self
+    }
+
+    ///
+    /// # The transformation from the user space to the viewport
+    ///
+    /// # Return
+    ///
+    /// None when the width or the height is relative
+    ///
+    #[must_use]
+    pub fn viewport_matrix(&self) -> Option<Matrix> {
+        let viewport = (self.width.to_px()?, self.height.to_px()?);
+        Some(
+            self.view_box
+                .to_viewport(viewport, self.aspect.unwrap_or_default()),
+        )
+    }
+
+    ///
+    /// # The bounding box of the content
+    ///
+    /// The texts are ignored and the uses are resolved.
+    ///
+    /// - `stroke` Include the half of the stroke width of the stroked elements
+    ///
+    /// # Return
+    ///
+    /// None when the document has no measurable content
+    ///
+    #[must_use]
+    pub fn bounding_box(&self, stroke: bool) -> Option<BoundingBox> {
+        let root = self.root.as_ref()?;
+        bounding_box(root, root, Matrix::IDENTITY, stroke)
+    }
+
+    ///
+    /// # The bounding box of an element in the document coordinates
+    ///
+    /// - `id`      The id of the element
+    /// - `stroke`  Include the half of the stroke width of the stroked elements
+    ///
+    /// # Return
+    ///
+    /// None when the element is missing or has no measurable content
+    ///
+    #[must_use]
+    pub fn element_bounding_box(&self, id: &str, stroke: bool) -> Option<BoundingBox> {
+        let root = self.root.as_ref()?;
+        let (node, matrix) = locate(root, id, Matrix::IDENTITY)?;
+        bounding_box(root, node, matrix, stroke)
+    }
+
+    ///
+    /// # Set the viewBox to the bounds of the content
+    ///
+    /// The strokes are included. Nothing is done when the content can not be
+    /// measured.
+    ///
+    /// - `padding` The space added around the content
+    ///
+    pub fn fit_view_box(&mut self, padding: f64) -> &mut Self {
+        if let Some(bounds) = self.bounding_box(true).map(|b| b.pad(padding)) {
+            self.view_box =
+                ViewBox::new(bounds.min_x, bounds.min_y, bounds.width(), bounds.height());
+            let view_box = self.view_box.to_string();
+            if let Some(root) = self.root.as_mut() {
+                root.set_attribute("viewBox", view_box.as_str());
+            }
+        }
+        self
+    }
+
+    ///
+    /// # Apply the transformations to the geometry
+    ///
+    /// The coordinates of the lines, polylines, polygons and paths are
+    /// rewritten, the other shapes keep their element when the transformation
+    /// allows it and become paths otherwise. The `transform` attributes of the
+    /// groups and links are removed. The elements which can not be rewritten,
+    /// like texts, images and uses, keep their accumulated transformation as a
+    /// matrix. The elements referenced by a `use` and their ancestors are not
+    /// flattened, the `use` draws them in their own coordinates.
+    ///
+    pub fn flatten_transforms(&mut self) -> &mut Self {
+        if let Some(root) = self.root.as_mut() {
+            let ids = referenced(root);
+            let width = length(root, "stroke-width").unwrap_or(1.0);
+            for child in root.elements_mut() {
+                flatten(child, Matrix::IDENTITY, ids.as_slice(), width);
+            }
+        }
+        self
+    }
+
+    ///
+    /// # The element currently open
+    ///
+    /// The svg element is reopened when every element is closed, the content
+    /// added after `end` is appended to it.
+    ///
+    /// # Return
+    ///
+    /// None before the call of `start`
+    ///
+    fn current(&mut self) -> Option<&mut Node> {
+        if self.open.is_empty() && self.root.is_some() {
+            self.open.push(0);
+        }
+        let (_, path) = self.open.split_first()?;
+        let mut node = self.root.as_mut()?;
+        for &index in path {
+            node = node.child_element_mut(index)?;
+        }
+        Some(node)
+    }
+
+    ///
+    /// # Open a new element inside the current element
+    ///
+    /// - `tag` The element name
+    ///
+    fn open(&mut self, tag: &str) -> &mut Self {
+        self.open_node(Node::new(tag))
+    }
+
+    fn open_node(&mut self, node: Node) -> &mut Self {
+        match self.current() {
+            Some(parent) => {
+                parent.children_mut().push(Child::Element(node));
+                let index = parent.children().len() - 1;
+                self.open.push(index);
+                self
+            }
+            None => self.fail(SvgError::NotStarted(node.tag().to_string())),
+        }
+    }
+
+    ///
+    /// # Append a closed element inside the current element
+    ///
+    pub(crate) fn append(&mut self, node: Node) -> &mut Self {
+        match self.current() {
+            Some(parent) => {
+                parent.append(node);
+                self
+            }
+            None => self.fail(SvgError::NotStarted(node.tag().to_string())),
+        }
+    }
+
+    ///
+    /// # Set an attribute on the current element
+    ///
+    fn attr(&mut self, name: &str, value: &str) -> &mut Self {
+        match self.current() {
+            Some(node) => {
+                node.set_attribute(name, value);
+                self
+            }
+            None => self.fail(SvgError::NotStarted(name.to_string())),
+        }
+    }
+
+    ///
+    /// # Close the nearest open element named `tag` and his open children
+    ///
+    fn close_element(&mut self, tag: &str) -> &mut Self {
+        let mut tags = Vec::new();
+        if let Some(mut node) = self.root.as_ref() {
+            tags.push(node.tag().to_string());
+            for &index in self.open.iter().skip(1) {
+                match node.children().get(index) {
+                    Some(Child::Element(n)) => node = n,
+                    _ => break,
+                }
+                tags.push(node.tag().to_string());
+            }
+        }
+        if let Some(depth) = tags.iter().rposition(|t| t == tag) {
+            self.open.truncate(depth);
+        }
+        self
+    }
+
+    ///
+    /// # Set a reference to a definition on the current element
+    ///
+    fn reference(&mut self, name: &str, id: &str) -> &mut Self {
+        self.attr(
+            name,
+            format!("url(#{})", id.trim_start_matches('#')).as_str(),
+        )
+    }
+
+    ///
+    /// # Set a paint attribute on the current element
+    ///
+    fn paint(&mut self, name: &str, paint: Result<Paint, SvgError>) -> &mut Self {
+        let opacity = format!("{name}-opacity");
+        match paint {
+            Ok(p) => match p.opacity() {
+                Some(a) => self
+                    .attr(name, p.to_string().as_str())
+                    .attr(opacity.as_str(), decimal(a, 6).as_str()),
+                None => {
+                    if let Some(node) = self.current() {
+                        node.remove_attribute(opacity.as_str());
+                    }
+                    self.attr(name, p.to_string().as_str())
+                }
+            },
+            Err(e) => self.fail(e),
+        }
+    }
+
+    ///
+    /// # Keep the first error to report it on save
+    ///
+    pub(crate) fn fail(&mut self, error: SvgError) -> &mut Self {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+        self
+    }
+
+    ///
+    /// # Serialize the document
+    ///
+    fn to_xml(&self) -> String {
+        let mut xml = match self.format {
+            Format::Minified => String::new(),
+            Format::Pretty { .. } | Format::Compact => String::from("<?xml version=\"1.0\"?>\n"),
+        };
+        if let Some(root) = &self.root {
+            if self.defs.is_empty() {
+                root.write(&mut xml, self.format, 0);
+            } else {
+                self.with_defs(root).write(&mut xml, self.format, 0);
+            }
+        }
+        xml
+    }
+
+    ///
+    /// # The children of the root, used as the content of a definition
+    ///
+    /// # Errors
+    ///
+    /// On a failure of a previous call
+    ///
+    pub(crate) fn into_content(mut self) -> Result<Vec<Child>, SvgError> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        Ok(match &self.root {
+            None => Vec::new(),
+            Some(root) if self.defs.is_empty() => root.children().to_vec(),
+            Some(root) => self.with_defs(root).children().to_vec(),
+        })
+    }
+
+    ///
+    /// # A copy of the root with the registered definitions
+    ///
+    /// The definitions are appended to the first `<defs>` child of the root,
+    /// created when missing.
+    ///
+    fn with_defs(&self, root: &Node) -> Node {
+        let mut root = root.clone();
+        let index = root
+            .children()
+            .iter()
+            .position(|c| matches!(c, Child::Element(e) if e.tag() == "defs"));
+        let index = index.unwrap_or_else(|| {
+            root.children_mut()
+                .insert(0, Child::Element(Node::new("defs")));
+            0
+        });
+        if let Some(defs) = root.child_element_mut(index) {
+            for definition in &self.defs {
+                defs.append(definition.clone());
+            }
+        }
+        root
+    }
+
+    ///
+    /// # Write the svg to a writer
+    ///
+    /// # Errors
+    ///
+    /// On a failure of a previous call and on write failure
+    ///
+    /// - `out` The destination
+    ///
+    pub fn write_to<W: Write>(&self, out: &mut W) -> Result<(), SvgError> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        out.write_all(self.to_xml().as_bytes())?;
+        Ok(())
+    }
+
+    ///
+    /// # Convert the svg to bytes
+    ///
+    /// # Errors
+    ///
+    /// On a failure of a previous call
+    ///
+    pub fn into_bytes(self) -> Result<Vec<u8>, SvgError> {
+        let mut bytes = Vec::new();
+        self.write_to(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    ///
+    /// # Check the well-formedness of the document
+    ///
+    /// # Return
+    ///
+    /// The diagnostics, empty for a well-formed document
+    ///
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        validate(self.to_xml().as_str())
+    }
+
+    ///
+    /// # Write the svg to disk
     ///
     /// # Errors
     ///
-    /// On dir creation failure
+    /// On a failure of a previous call, when the document is not well-formed,
+    /// in which case no file is created, on dir or file creation failure and,
+    /// with the `xmllint` feature, when xmllint is not installed or rejects
+    /// the document
     ///
     /// - `dir`         The directory to save the svg
     /// - `filename`    The filename without the extension
     ///
     /// # Return
     ///
-    /// 0 On success
-    /// 1 on lint failure
+    /// The path of the created file
     ///
-    pub fn save(&mut self, dir: &str, filename: &str) -> i32 {
-        if !Path::new(dir).exists() {
-            fs::create_dir(dir).expect("failed to create the directory");
+    pub fn save(&self, dir: &str, filename: &str) -> Result<PathBuf, SvgError> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        let xml = self.to_xml();
+        if let Some(diagnostic) = validate(xml.as_str()).into_iter().next() {
+            return Err(SvgError::Validation {
+                line: diagnostic.line,
+                column: diagnostic.column,
+                message: diagnostic.message,
+            });
         }
-        let file = format!("{dir}/{filename}.svg");
-        let mut f = File::create(file.as_str()).expect("failed to create the file");
-        f.write_all(self.svg.as_bytes()).expect("");
-        assert!(f.sync_data().is_ok());
-        if !Command::new("xmllint")
+        fs::create_dir_all(dir)?;
+        let file = Path::new(dir).join(format!("{filename}.svg"));
+        let mut f = File::create(file.as_path())?;
+        f.write_all(xml.as_bytes())?;
+        f.sync_data()?;
+        #[cfg(feature = "xmllint")]
+        Self::lint(file.as_path())?;
+        Ok(file)
+    }
+
+    ///
+    /// # Write the svg to disk
+    ///
+    /// - `dir`         The directory to save the svg
+    /// - `filename`    The filename without the extension
+    ///
+    /// # Return
+    ///
+    /// 0 On success
+    /// 1 on failure
+    ///
+    #[deprecated(note = "use save to get the failure reason")]
+    pub fn save_status(&self, dir: &str, filename: &str) -> i32 {
+        i32::from(self.save(dir, filename).is_err())
+    }
+
+    ///
+    /// # Check the document with xmllint
+    ///
+    /// - `file` The svg to check
+    ///
+    #[cfg(feature = "xmllint")]
+    fn lint(file: &Path) -> Result<(), SvgError> {
+        let output = match std::process::Command::new("xmllint")
             .arg("--pedantic")
-            .arg(file.as_str())
-            .stderr(File::create(format!("{filename}_check")).expect("failed to create the file"))
+            .arg("--noout")
+            .arg(file)
             .output()
-            .unwrap()
-            .status
-            .success()
         {
-            return 1;
+            Ok(output) => output,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(SvgError::MissingTool("xmllint".to_string()));
+            }
+            Err(e) => return Err(SvgError::Io(e)),
+        };
+        if output.status.success() {
+            return Ok(());
+        }
+        let report = String::from_utf8_lossy(output.stderr.as_slice());
+        let mut lines = report.lines();
+        while let Some(line) = lines.next() {
+            let Some((location, message)) = line.split_once(" error : ") else {
+                continue;
+            };
+            let line = location
+                .rsplit(':')
+                .nth(1)
+                .and_then(|l| l.trim().parse().ok())
+                .unwrap_or(0);
+            let column = lines.nth(1).and_then(|c| c.find('^')).map_or(0, |c| c + 1);
+            return Err(SvgError::Validation {
+                line,
+                column,
+                message: message.to_string(),
+            });
         }
-        0
+        Err(SvgError::ToolFailed {
+            tool: "xmllint".to_string(),
+            code: output.status.code(),
+        })
     }
 }
@@ -0,0 +1,457 @@
+use crate::creator::error::SvgError;
+use crate::creator::format::{decimal, number_len};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+///
+/// # The number of decimals written in the transformations
+///
+const PRECISION: usize = 6;
+
+///
+/// # An affine transformation matrix
+///
+/// The matrix `[a c e; b d f; 0 0 1]` as written by `matrix(a,b,c,d,e,f)`.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix {
+    pub const IDENTITY: Self = Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    ///
+    /// # Constructor
+    ///
+    #[must_use]
+    pub const fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    ///
+    /// # The product of two matrix
+    ///
+    /// The `other` transformation is applied first.
+    ///
+    /// - `other` The right operand
+    ///
+    #[must_use]
+    pub fn multiply(self, other: Self) -> Self {
+        Self::new(
+            self.a.mul_add(other.a, self.c * other.b),
+            self.b.mul_add(other.a, self.d * other.b),
+            self.a.mul_add(other.c, self.c * other.d),
+            self.b.mul_add(other.c, self.d * other.d),
+            self.a.mul_add(other.e, self.c.mul_add(other.f, self.e)),
+            self.b.mul_add(other.e, self.d.mul_add(other.f, self.f)),
+        )
+    }
+
+    ///
+    /// # The inverse matrix
+    ///
+    /// None when the matrix can not be inverted
+    ///
+    #[must_use]
+    pub fn inverse(self) -> Option<Self> {
+        let det = self.a.mul_add(self.d, -self.b * self.c);
+        if det.abs() < f64::EPSILON {
+            return None;
+        }
+        Some(Self::new(
+            self.d / det,
+            -self.b / det,
+            -self.c / det,
+            self.a / det,
+            self.c.mul_add(self.f, -self.d * self.e) / det,
+            self.b.mul_add(self.e, -self.a * self.f) / det,
+        ))
+    }
+
+    ///
+    /// # Transform a point
+    ///
+    /// - `point` The point to transform
+    ///
+    #[must_use]
+    pub fn apply(self, point: (f64, f64)) -> (f64, f64) {
+        let (x, y) = point;
+        (
+            self.a.mul_add(x, self.c.mul_add(y, self.e)),
+            self.b.mul_add(x, self.d.mul_add(y, self.f)),
+        )
+    }
+
+    ///
+    /// # Transform a vector, ignoring the translation
+    ///
+    /// - `vector` The vector to transform
+    ///
+    #[must_use]
+    pub fn apply_vector(self, vector: (f64, f64)) -> (f64, f64) {
+        let (x, y) = vector;
+        (self.a.mul_add(x, self.c * y), self.b.mul_add(x, self.d * y))
+    }
+
+    ///
+    /// # Check if the matrix does nothing
+    ///
+    #[must_use]
+    pub fn is_identity(self) -> bool {
+        [self.a - 1.0, self.b, self.c, self.d - 1.0, self.e, self.f]
+            .iter()
+            .all(|v| v.abs() < 1e-12)
+    }
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let values =
+            [self.a, self.b, self.c, self.d, self.e, self.f].map(|v| decimal(v, PRECISION));
+        write!(f, "matrix({})", values.join(","))
+    }
+}
+
+///
+/// # A transformation of a transform list
+///
+/// The angles are in degrees.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    Translate(f64, f64),
+    Scale(f64, f64),
+    Rotate {
+        angle: f64,
+        center: Option<(f64, f64)>,
+    },
+    SkewX(f64),
+    SkewY(f64),
+    Matrix(Matrix),
+}
+
+impl Operation {
+    ///
+    /// # The matrix of the operation
+    ///
+    #[must_use]
+    pub fn to_matrix(self) -> Matrix {
+        match self {
+            Self::Translate(tx, ty) => Matrix::new(1.0, 0.0, 0.0, 1.0, tx, ty),
+            Self::Scale(sx, sy) => Matrix::new(sx, 0.0, 0.0, sy, 0.0, 0.0),
+            Self::Rotate { angle, center } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let rotation = Matrix::new(cos, sin, -sin, cos, 0.0, 0.0);
+                center.map_or(rotation, |(cx, cy)| {
+                    Matrix::new(1.0, 0.0, 0.0, 1.0, cx, cy)
+                        .multiply(rotation)
+                        .multiply(Matrix::new(1.0, 0.0, 0.0, 1.0, -cx, -cy))
+                })
+            }
+            Self::SkewX(angle) => Matrix::new(1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0),
+            Self::SkewY(angle) => Matrix::new(1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0),
+            Self::Matrix(matrix) => matrix,
+        }
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let n = |v: f64| decimal(v, PRECISION);
+        match *self {
+            Self::Translate(tx, ty) => write!(f, "translate({},{})", n(tx), n(ty)),
+            Self::Scale(sx, sy) if (sx - sy).abs() < f64::EPSILON => write!(f, "scale({})", n(sx)),
+            Self::Scale(sx, sy) => write!(f, "scale({},{})", n(sx), n(sy)),
+            Self::Rotate {
+                angle,
+                center: None,
+            } => write!(f, "rotate({})", n(angle)),
+            Self::Rotate {
+                angle,
+                center: Some((cx, cy)),
+            } => write!(f, "rotate({},{},{})", n(angle), n(cx), n(cy)),
+            Self::SkewX(angle) => write!(f, "skewX({})", n(angle)),
+            Self::SkewY(angle) => write!(f, "skewY({})", n(angle)),
+            Self::Matrix(matrix) => write!(f, "{matrix}"),
+        }
+    }
+}
+
+///
+/// # A transform list
+///
+/// The operations are written in the order of the `transform` attribute: the
+/// last one is applied first to the coordinates.
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Transform {
+    operations: Vec<Operation>,
+}
+
+impl Transform {
+    ///
+    /// # Constructor
+    ///
+    /// The identity transformation
+    ///
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            operations: Vec::new(),
+        }
+    }
+
+    ///
+    /// # The operations of the list
+    ///
+    #[must_use]
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    ///
+    /// # Add an operation
+    ///
+    /// - `operation` The operation
+    ///
+    #[must_use]
+    pub fn push(mut self, operation: Operation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    ///
+    /// # Add a translation
+    ///
+    #[must_use]
+    pub fn translate(self, tx: f64, ty: f64) -> Self {
+        self.push(Operation::Translate(tx, ty))
+    }
+
+    ///
+    /// # Add a scaling
+    ///
+    #[must_use]
+    pub fn scale(self, sx: f64, sy: f64) -> Self {
+        self.push(Operation::Scale(sx, sy))
+    }
+
+    ///
+    /// # Add a rotation around the origin
+    ///
+    /// - `angle` The angle in degrees, clockwise
+    ///
+    #[must_use]
+    pub fn rotate(self, angle: f64) -> Self {
+        self.push(Operation::Rotate {
+            angle,
+            center: None,
+        })
+    }
+
+    ///
+    /// # Add a rotation around a point
+    ///
+    /// - `angle`   The angle in degrees, clockwise
+    /// - `center`  The center of the rotation
+    ///
+    #[must_use]
+    pub fn rotate_around(self, angle: f64, center: (f64, f64)) -> Self {
+        self.push(Operation::Rotate {
+            angle,
+            center: Some(center),
+        })
+    }
+
+    ///
+    /// # Add a skew along the x axis
+    ///
+    /// - `angle` The angle in degrees
+    ///
+    #[must_use]
+    pub fn skew_x(self, angle: f64) -> Self {
+        self.push(Operation::SkewX(angle))
+    }
+
+    ///
+    /// # Add a skew along the y axis
+    ///
+    /// - `angle` The angle in degrees
+    ///
+    #[must_use]
+    pub fn skew_y(self, angle: f64) -> Self {
+        self.push(Operation::SkewY(angle))
+    }
+
+    ///
+    /// # Add a matrix
+    ///
+    #[must_use]
+    pub fn matrix(self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        self.push(Operation::Matrix(Matrix::new(a, b, c, d, e, f)))
+    }
+
+    ///
+    /// # Compose with another transformation
+    ///
+    /// The `other` transformation is applied first to the coordinates, like
+    /// a transform nested in this one.
+    ///
+    /// - `other` The inner transformation
+    ///
+    #[must_use]
+    pub fn then(mut self, other: &Self) -> Self {
+        self.operations.extend_from_slice(&other.operations);
+        self
+    }
+
+    ///
+    /// # The matrix of the whole list
+    ///
+    #[must_use]
+    pub fn to_matrix(&self) -> Matrix {
+        self.operations
+            .iter()
+            .fold(Matrix::IDENTITY, |m, o| m.multiply(o.to_matrix()))
+    }
+
+    ///
+    /// # The list collapsed in a single matrix
+    ///
+    #[must_use]
+    pub fn collapse(&self) -> Self {
+        Self::new().push(Operation::Matrix(self.to_matrix()))
+    }
+
+    ///
+    /// # The inverse transformation
+    ///
+    /// None when the transformation can not be inverted
+    ///
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        Some(Self::new().push(Operation::Matrix(self.to_matrix().inverse()?)))
+    }
+
+    ///
+    /// # Transform a point
+    ///
+    /// - `point` The point to transform
+    ///
+    #[must_use]
+    pub fn apply(&self, point: (f64, f64)) -> (f64, f64) {
+        self.to_matrix().apply(point)
+    }
+
+    ///
+    /// # Check if the transformation does nothing
+    ///
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        self.to_matrix().is_identity()
+    }
+}
+
+impl Display for Transform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, operation) in self.operations.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{operation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Matrix> for Transform {
+    fn from(matrix: Matrix) -> Self {
+        Self::new().push(Operation::Matrix(matrix))
+    }
+}
+
+impl FromStr for Transform {
+    type Err = SvgError;
+
+    ///
+    /// # Parse a `transform` attribute
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SvgError::InvalidTransform(s.to_string());
+        let mut transform = Self::new();
+        let mut rest = s.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        while !rest.is_empty() {
+            let open = rest.find('(').ok_or_else(invalid)?;
+            let close = rest.find(')').ok_or_else(invalid)?;
+            if close < open {
+                return Err(invalid());
+            }
+            let name = rest[..open].trim();
+            let values = numbers(&rest[open + 1..close]).ok_or_else(invalid)?;
+            transform = transform.push(match (name, values.as_slice()) {
+                ("matrix", &[a, b, c, d, e, f]) => Operation::Matrix(Matrix::new(a, b, c, d, e, f)),
+                ("translate", &[tx]) => Operation::Translate(tx, 0.0),
+                ("translate", &[tx, ty]) => Operation::Translate(tx, ty),
+                ("scale", &[s]) => Operation::Scale(s, s),
+                ("scale", &[sx, sy]) => Operation::Scale(sx, sy),
+                ("rotate", &[angle]) => Operation::Rotate {
+                    angle,
+                    center: None,
+                },
+                ("rotate", &[angle, cx, cy]) => Operation::Rotate {
+                    angle,
+                    center: Some((cx, cy)),
+                },
+                ("skewX", &[angle]) => Operation::SkewX(angle),
+                ("skewY", &[angle]) => Operation::SkewY(angle),
+                _ => return Err(invalid()),
+            });
+            rest =
+                rest[close + 1..].trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        }
+        Ok(transform)
+    }
+}
+
+impl TryFrom<&str> for Transform {
+    type Error = SvgError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+///
+/// # Parse a list of numbers separated by spaces or commas
+///
+pub(crate) fn numbers(data: &str) -> Option<Vec<f64>> {
+    let mut values = Vec::new();
+    let mut rest = data.trim();
+    while !rest.is_empty() {
+        let len = number_len(rest);
+        if len == 0 {
+            return None;
+        }
+        values.push(rest[..len].parse().ok()?);
+        rest = rest[len..].trim_start();
+        if let Some(next) = rest.strip_prefix(',') {
+            rest = next.trim_start();
+            if rest.is_empty() {
+                return None;
+            }
+        }
+    }
+    Some(values)
+}
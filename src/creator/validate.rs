@@ -0,0 +1,447 @@
+use std::fmt::{Display, Formatter};
+
+///
+/// # A well-formedness error
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    ///
+    /// # The line of the error, starting at 1
+    ///
+    pub line: usize,
+    ///
+    /// # The column of the error, starting at 1
+    ///
+    pub column: usize,
+    ///
+    /// # The byte offset of the error
+    ///
+    pub offset: usize,
+    ///
+    /// # The error description
+    ///
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+///
+/// # Check the well-formedness of a xml document
+///
+/// Check the balance of the tags, the quoting and the unicity of the
+/// attributes, the entity references and the namespace prefixes.
+///
+/// - `xml` The document to check
+///
+/// # Return
+///
+/// The diagnostics in document order, empty for a well-formed document
+///
+#[must_use]
+pub fn validate(xml: &str) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        xml,
+        pos: 0,
+        open: Vec::new(),
+        scopes: Vec::new(),
+        root: false,
+        diagnostics: Vec::new(),
+    };
+    validator.document();
+    validator.diagnostics
+}
+
+struct Validator<'a> {
+    xml: &'a str,
+    pos: usize,
+    open: Vec<&'a str>,
+    scopes: Vec<Vec<&'a str>>,
+    root: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&mut self, offset: usize, message: String) {
+        let before = &self.xml[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rfind('\n')
+            .map_or(before, |n| &before[n + 1..])
+            .chars()
+            .count()
+            + 1;
+        self.diagnostics.push(Diagnostic {
+            line,
+            column,
+            offset,
+            message,
+        });
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.xml[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let rest = self.rest();
+        let trimmed = rest.trim_start_matches(is_whitespace);
+        self.pos += rest.len() - trimmed.len();
+        rest.len() != trimmed.len()
+    }
+
+    ///
+    /// # Skip after the next `end`
+    ///
+    /// Return false when `end` is missing
+    ///
+    fn skip_after(&mut self, end: &str) -> bool {
+        match self.rest().find(end) {
+            Some(i) => {
+                self.pos += i + end.len();
+                true
+            }
+            None => {
+                self.pos = self.xml.len();
+                false
+            }
+        }
+    }
+
+    ///
+    /// # Skip a malformed token in a tag
+    ///
+    fn skip_token(&mut self) {
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(i, c)| is_whitespace(c) || c == '>' || rest[i..].starts_with("/>"))
+            .map_or(rest.len(), |(i, _)| i);
+        self.pos += len;
+    }
+
+    fn name(&mut self) -> Option<&'a str> {
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|&(i, c)| {
+                if i == 0 {
+                    !is_name_start(c)
+                } else {
+                    !is_name_char(c)
+                }
+            })
+            .map_or(rest.len(), |(i, _)| i);
+        if len == 0 {
+            return None;
+        }
+        self.pos += len;
+        Some(&rest[..len])
+    }
+
+    fn document(&mut self) {
+        while self.pos < self.xml.len() {
+            let start = self.pos;
+            let rest = self.rest();
+            if rest.starts_with("<?") {
+                self.processing_instruction(start);
+            } else if rest.starts_with("<!--") {
+                self.comment(start);
+            } else if rest.starts_with("<![CDATA[") {
+                if self.open.is_empty() {
+                    self.error(start, "CDATA section outside the root element".to_string());
+                }
+                if !self.skip_after("]]>") {
+                    self.error(start, "unterminated CDATA section".to_string());
+                }
+            } else if rest.starts_with("<!DOCTYPE") {
+                if self.root || !self.open.is_empty() {
+                    self.error(start, "misplaced doctype declaration".to_string());
+                }
+                self.doctype(start);
+            } else if rest.starts_with("</") {
+                self.end_tag(start);
+            } else if rest.starts_with('<') {
+                self.start_tag(start);
+            } else {
+                self.text(start);
+            }
+        }
+        let end = self.xml.len();
+        if let Some(tag) = self.open.last() {
+            let message = format!("premature end of data, {tag} is not closed");
+            self.error(end, message);
+        } else if !self.root {
+            self.error(end, "no root element".to_string());
+        }
+    }
+
+    fn processing_instruction(&mut self, start: usize) {
+        self.pos += 2;
+        let target = self.name();
+        if target.is_some_and(|t| t.eq_ignore_ascii_case("xml")) && start != 0 {
+            self.error(
+                start,
+                "xml declaration allowed only at the start of the document".to_string(),
+            );
+        }
+        if target.is_none() {
+            self.error(start, "processing instruction without target".to_string());
+        }
+        if !self.skip_after("?>") {
+            self.error(start, "unterminated processing instruction".to_string());
+        }
+    }
+
+    fn comment(&mut self, start: usize) {
+        self.pos += 4;
+        match self.rest().find("--") {
+            Some(i) if self.rest()[i..].starts_with("-->") => self.pos += i + 3,
+            Some(i) => {
+                self.error(self.pos + i, "double hyphen within comment".to_string());
+                self.skip_after("-->");
+            }
+            None => {
+                self.error(start, "unterminated comment".to_string());
+                self.pos = self.xml.len();
+            }
+        }
+    }
+
+    fn doctype(&mut self, start: usize) {
+        let mut depth = 0;
+        for (i, c) in self.rest().char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                '>' if depth == 0 => {
+                    self.pos += i + 1;
+                    return;
+                }
+                _ => {}
+            }
+        }
+        self.error(start, "unterminated doctype declaration".to_string());
+        self.pos = self.xml.len();
+    }
+
+    fn end_tag(&mut self, start: usize) {
+        self.pos += 2;
+        let Some(name) = self.name() else {
+            self.error(self.pos, "invalid end tag name".to_string());
+            self.skip_after(">");
+            return;
+        };
+        self.skip_whitespace();
+        if self.peek() == Some('>') {
+            self.pos += 1;
+        } else {
+            self.error(self.pos, format!("expected '>' to end the tag {name}"));
+            self.skip_after(">");
+        }
+        match self.open.pop() {
+            Some(open) if open == name => {}
+            Some(open) => {
+                self.error(
+                    start,
+                    format!("opening and ending tag mismatch: {open} and {name}"),
+                );
+            }
+            None => self.error(start, format!("unexpected end tag {name}")),
+        }
+        self.scopes.pop();
+    }
+
+    fn start_tag(&mut self, start: usize) {
+        self.pos += 1;
+        let Some(name) = self.name() else {
+            self.error(self.pos, "invalid start tag name".to_string());
+            self.skip_after(">");
+            return;
+        };
+        if self.open.is_empty() {
+            if self.root {
+                self.error(
+                    start,
+                    "extra content at the end of the document".to_string(),
+                );
+            }
+            self.root = true;
+        }
+        let mut attributes: Vec<(&str, usize)> = Vec::new();
+        let mut declared = Vec::new();
+        let closed;
+        loop {
+            let separated = self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                closed = true;
+                break;
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                closed = false;
+                break;
+            }
+            let at = self.pos;
+            if rest.is_empty() {
+                self.error(start, format!("unterminated start tag {name}"));
+                return;
+            }
+            let Some(attribute) = self.name() else {
+                self.error(at, format!("invalid attribute in the tag {name}"));
+                self.skip_token();
+                continue;
+            };
+            if !separated {
+                self.error(
+                    at,
+                    format!("attributes of {name} must be separated by whitespace"),
+                );
+            }
+            if attributes.iter().any(|(a, _)| *a == attribute) {
+                self.error(at, format!("attribute {attribute} redefined"));
+            }
+            attributes.push((attribute, at));
+            self.skip_whitespace();
+            if self.peek() != Some('=') {
+                self.error(
+                    self.pos,
+                    format!("expected '=' after the attribute {attribute}"),
+                );
+                continue;
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let Some(quote) = self.peek().filter(|q| *q == '"' || *q == '\'') else {
+                self.error(
+                    self.pos,
+                    format!("value of the attribute {attribute} must be quoted"),
+                );
+                self.skip_token();
+                continue;
+            };
+            self.pos += 1;
+            let value_start = self.pos;
+            let Some(len) = self.rest().find(quote) else {
+                self.error(
+                    value_start,
+                    format!("unterminated value of the attribute {attribute}"),
+                );
+                self.pos = self.xml.len();
+                return;
+            };
+            let value = &self.rest()[..len];
+            if let Some(i) = value.find('<') {
+                self.error(
+                    value_start + i,
+                    format!("'<' in the value of the attribute {attribute}"),
+                );
+            }
+            self.references(value, value_start);
+            self.pos += len + 1;
+            if attribute == "xmlns" {
+                declared.push("");
+            } else if let Some(prefix) = attribute.strip_prefix("xmlns:") {
+                if value.is_empty() {
+                    self.error(
+                        value_start,
+                        format!("empty namespace for the prefix {prefix}"),
+                    );
+                }
+                declared.push(prefix);
+            }
+        }
+        self.scopes.push(declared);
+        self.prefix(name, start + 1);
+        for (attribute, at) in attributes {
+            if attribute != "xmlns" && !attribute.starts_with("xmlns:") {
+                self.prefix(attribute, at);
+            }
+        }
+        if closed {
+            self.scopes.pop();
+        } else {
+            self.open.push(name);
+        }
+    }
+
+    ///
+    /// # Check that the prefix of a qualified name is declared
+    ///
+    fn prefix(&mut self, name: &str, at: usize) {
+        let Some((prefix, local)) = name.split_once(':') else {
+            return;
+        };
+        if prefix.is_empty() || local.is_empty() || local.contains(':') {
+            self.error(at, format!("invalid qualified name {name}"));
+            return;
+        }
+        if prefix == "xml" {
+            return;
+        }
+        if !self.scopes.iter().flatten().any(|p| *p == prefix) {
+            self.error(
+                at,
+                format!("namespace prefix {prefix} of {name} is not defined"),
+            );
+        }
+    }
+
+    fn text(&mut self, start: usize) {
+        let len = self.rest().find('<').unwrap_or(self.rest().len());
+        let text = &self.rest()[..len];
+        if self.open.is_empty() && !text.chars().all(is_whitespace) {
+            let offset = start + (text.len() - text.trim_start_matches(is_whitespace).len());
+            self.error(offset, "content outside the root element".to_string());
+        }
+        if let Some(i) = text.find("]]>") {
+            self.error(start + i, "']]>' not allowed in content".to_string());
+        }
+        self.references(text, start);
+        self.pos += len;
+    }
+
+    ///
+    /// # Check the entity and character references
+    ///
+    fn references(&mut self, data: &str, offset: usize) {
+        for (i, _) in data.match_indices('&') {
+            let reference = &data[i + 1..];
+            let valid = reference.find(';').is_some_and(|end| {
+                let entity = &reference[..end];
+                if let Some(hex) = entity.strip_prefix("#x") {
+                    !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    !dec.is_empty() && dec.chars().all(|c| c.is_ascii_digit())
+                } else {
+                    matches!(entity, "amp" | "lt" | "gt" | "quot" | "apos")
+                }
+            });
+            if !valid {
+                self.error(offset + i, "invalid entity reference".to_string());
+            }
+        }
+    }
+}
+
+const fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\r' | '\n')
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == ':'
+}
+
+fn is_name_char(c: char) -> bool {
+    is_name_start(c) || c.is_numeric() || c == '-' || c == '.'
+}
@@ -0,0 +1,287 @@
+use crate::creator::error::SvgError;
+use crate::creator::format::decimal;
+use crate::creator::transform::{numbers, Matrix};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+///
+/// # The rectangle of the user space shown in the viewport
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ViewBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ViewBox {
+    ///
+    /// # Constructor
+    ///
+    /// - `min_x`   The left of the visible area
+    /// - `min_y`   The top of the visible area
+    /// - `width`   The width of the visible area
+    /// - `height`  The height of the visible area
+    ///
+    #[must_use]
+    pub const fn new(min_x: f64, min_y: f64, width: f64, height: f64) -> Self {
+        Self {
+            min_x,
+            min_y,
+            width,
+            height,
+        }
+    }
+
+    ///
+    /// # The transformation from the user space to the viewport
+    ///
+    /// - `viewport`    The width and the height of the viewport
+    /// - `aspect`      The alignment of the view box in the viewport
+    ///
+    #[must_use]
+    pub fn to_viewport(&self, viewport: (f64, f64), aspect: PreserveAspectRatio) -> Matrix {
+        let (width, height) = viewport;
+        let mut sx = if self.width > 0.0 {
+            width / self.width
+        } else {
+            1.0
+        };
+        let mut sy = if self.height > 0.0 {
+            height / self.height
+        } else {
+            1.0
+        };
+        let align = match aspect {
+            PreserveAspectRatio::None => None,
+            PreserveAspectRatio::Meet(align) => {
+                sx = sx.min(sy);
+                sy = sx;
+                Some(align)
+            }
+            PreserveAspectRatio::Slice(align) => {
+                sx = sx.max(sy);
+                sy = sx;
+                Some(align)
+            }
+        };
+        let mut tx = -self.min_x * sx;
+        let mut ty = -self.min_y * sy;
+        if let Some(align) = align {
+            let (x, y) = align.factors();
+            tx += x * self.width.mul_add(-sx, width);
+            ty += y * self.height.mul_add(-sy, height);
+        }
+        Matrix::new(sx, 0.0, 0.0, sy, tx, ty)
+    }
+
+    ///
+    /// # Map a point of the user space to the viewport
+    ///
+    /// - `point`       The point in user units
+    /// - `viewport`    The width and the height of the viewport
+    /// - `aspect`      The alignment of the view box in the viewport
+    ///
+    #[must_use]
+    pub fn user_to_viewport(
+        &self,
+        point: (f64, f64),
+        viewport: (f64, f64),
+        aspect: PreserveAspectRatio,
+    ) -> (f64, f64) {
+        self.to_viewport(viewport, aspect).apply(point)
+    }
+
+    ///
+    /// # Map a point of the viewport to the user space
+    ///
+    /// - `point`       The point in the viewport
+    /// - `viewport`    The width and the height of the viewport
+    /// - `aspect`      The alignment of the view box in the viewport
+    ///
+    /// # Return
+    ///
+    /// None when the viewport is empty
+    ///
+    #[must_use]
+    pub fn viewport_to_user(
+        &self,
+        point: (f64, f64),
+        viewport: (f64, f64),
+        aspect: PreserveAspectRatio,
+    ) -> Option<(f64, f64)> {
+        Some(self.to_viewport(viewport, aspect).inverse()?.apply(point))
+    }
+}
+
+impl Display for ViewBox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let values = [self.min_x, self.min_y, self.width, self.height].map(|v| decimal(v, 6));
+        f.write_str(values.join(" ").as_str())
+    }
+}
+
+impl FromStr for ViewBox {
+    type Err = SvgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match numbers(s).as_deref() {
+            Some(&[min_x, min_y, width, height]) if width >= 0.0 && height >= 0.0 => {
+                Ok(Self::new(min_x, min_y, width, height))
+            }
+            _ => Err(SvgError::InvalidViewBox(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&str> for ViewBox {
+    type Error = SvgError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<(f64, f64, f64, f64)> for ViewBox {
+    fn from((min_x, min_y, width, height): (f64, f64, f64, f64)) -> Self {
+        Self::new(min_x, min_y, width, height)
+    }
+}
+
+///
+/// # The position of the view box in the viewport
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    XMinYMin,
+    XMidYMin,
+    XMaxYMin,
+    XMinYMid,
+    #[default]
+    XMidYMid,
+    XMaxYMid,
+    XMinYMax,
+    XMidYMax,
+    XMaxYMax,
+}
+
+impl Align {
+    const ALL: [Self; 9] = [
+        Self::XMinYMin,
+        Self::XMidYMin,
+        Self::XMaxYMin,
+        Self::XMinYMid,
+        Self::XMidYMid,
+        Self::XMaxYMid,
+        Self::XMinYMax,
+        Self::XMidYMax,
+        Self::XMaxYMax,
+    ];
+
+    ///
+    /// # The attribute keyword
+    ///
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::XMinYMin => "xMinYMin",
+            Self::XMidYMin => "xMidYMin",
+            Self::XMaxYMin => "xMaxYMin",
+            Self::XMinYMid => "xMinYMid",
+            Self::XMidYMid => "xMidYMid",
+            Self::XMaxYMid => "xMaxYMid",
+            Self::XMinYMax => "xMinYMax",
+            Self::XMidYMax => "xMidYMax",
+            Self::XMaxYMax => "xMaxYMax",
+        }
+    }
+
+    ///
+    /// # The part of the free space placed before the view box
+    ///
+    const fn factors(self) -> (f64, f64) {
+        match self {
+            Self::XMinYMin => (0.0, 0.0),
+            Self::XMidYMin => (0.5, 0.0),
+            Self::XMaxYMin => (1.0, 0.0),
+            Self::XMinYMid => (0.0, 0.5),
+            Self::XMidYMid => (0.5, 0.5),
+            Self::XMaxYMid => (1.0, 0.5),
+            Self::XMinYMax => (0.0, 1.0),
+            Self::XMidYMax => (0.5, 1.0),
+            Self::XMaxYMax => (1.0, 1.0),
+        }
+    }
+}
+
+impl Display for Align {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+///
+/// # The value of the `preserveAspectRatio` attribute
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreserveAspectRatio {
+    ///
+    /// # Stretch the view box to the viewport
+    ///
+    None,
+    ///
+    /// # Scale the view box to be entirely visible
+    ///
+    Meet(Align),
+    ///
+    /// # Scale the view box to cover the whole viewport
+    ///
+    Slice(Align),
+}
+
+impl Default for PreserveAspectRatio {
+    fn default() -> Self {
+        Self::Meet(Align::default())
+    }
+}
+
+impl Display for PreserveAspectRatio {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("none"),
+            Self::Meet(align) => write!(f, "{align}"),
+            Self::Slice(align) => write!(f, "{align} slice"),
+        }
+    }
+}
+
+impl FromStr for PreserveAspectRatio {
+    type Err = SvgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SvgError::InvalidViewBox(s.to_string());
+        let mut words = s.split_ascii_whitespace();
+        let align = words.next().ok_or_else(invalid)?;
+        let slice = match words.next() {
+            None | Some("meet") => false,
+            Some("slice") => true,
+            Some(_) => return Err(invalid()),
+        };
+        if words.next().is_some() {
+            return Err(invalid());
+        }
+        if align == "none" {
+            return Ok(Self::None);
+        }
+        let align = Align::ALL
+            .into_iter()
+            .find(|a| a.as_str() == align)
+            .ok_or_else(invalid)?;
+        Ok(if slice {
+            Self::Slice(align)
+        } else {
+            Self::Meet(align)
+        })
+    }
+}
@@ -1,50 +1,106 @@
 pub mod creator {
+    pub mod animation;
+    pub mod clip;
+    pub mod color;
+    pub mod definition;
+    pub mod effect;
+    pub mod element;
+    pub mod error;
+    pub mod fetch;
+    pub mod filter;
+    pub mod format;
+    pub mod geometry;
+    pub mod gradient;
+    pub mod image;
+    pub mod length;
+    pub mod marker;
+    pub mod mask;
+    pub mod node;
+    pub mod path;
+    pub mod pattern;
     pub mod svg;
+    pub mod transform;
+    pub mod validate;
+    pub mod viewport;
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::creator::animation::{
+        Animate, AnimateMotion, AnimateTransform, Begin, CalcMode, Easing, MotionRotate, Repeat,
+        Set, TransformType,
+    };
+    use crate::creator::clip::ClipPath;
+    use crate::creator::color::{Color, Paint};
+    use crate::creator::definition::{Definition, Units};
+    use crate::creator::effect;
+    use crate::creator::element::Shapes;
+    use crate::creator::error::SvgError;
+    use crate::creator::fetch::{FileFetcher, OfflineFetcher, ResourceFetcher};
+    use crate::creator::filter::{
+        BlendMode, Channel, ColorInterpolation, ColorMatrix, CompositeOperator, EdgeMode, Filter,
+        Input, Light, MorphologyOperator, TransferFunction, TurbulenceType,
+    };
+    use crate::creator::format::Format;
+    use crate::creator::geometry::BoundingBox;
+    use crate::creator::gradient::{LinearGradient, RadialGradient, SpreadMethod, Stop};
+    use crate::creator::image::{base64, MimeType};
+    use crate::creator::length::{Length, Unit};
+    use crate::creator::marker::{Marker, MarkerUnits, Orient};
+    use crate::creator::mask::{Mask, MaskType};
+    use crate::creator::path::{parse, Command, PathBuilder};
+    use crate::creator::pattern::Pattern;
     use crate::creator::svg::Svg;
+    use crate::creator::transform::{Matrix, Transform};
+    use crate::creator::validate::validate;
+    use crate::creator::viewport::{Align, PreserveAspectRatio, ViewBox};
+    use std::time::Duration;
+
+    const ICON: &[u8] = b"<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\"><path d=\"M0 0h24v24H0z\"/></svg>";
+
+    fn icons() -> OfflineFetcher {
+        OfflineFetcher::new()
+            .insert("https://simpleicons.org/icons/rust.svg", ICON)
+            .insert("https://simpleicons.org/icons/grunt.svg", ICON)
+    }
     #[test]
     pub fn japan() {
-        assert_eq!(
-            Svg::new(900.0, "px", 600.0, "px", "0 0 900 600", "japan")
+        assert!(
+            Svg::new(Length::px(900.0), Length::px(600.0), "0 0 900 600", "japan")
                 .start()
                 .rect()
-                .width(900.0, "px")
-                .height(600.0, "px")
+                .width(Length::px(900.0))
+                .height(Length::px(600.0))
                 .fill("#fff")
                 .close()
                 .circle()
-                .cx(450.0, "px")
-                .cy(300.0, "px")
-                .r(180.0, "px")
+                .cx(Length::px(450.0))
+                .cy(Length::px(300.0))
+                .r(Length::px(180.0))
                 .fill("#bc002d")
                 .close()
                 .end()
-                .save("flags", "japan"),
-            0
+                .save("flags", "japan")
+                .is_ok()
         );
     }
     #[test]
     pub fn clippy() {
-        assert_eq!(
-            Svg::badge(
-                true,
-                "clippy",
-                "failed",
-                "success",
-                "https://simpleicons.org/icons/rust.svg",
-                "badges",
-                "clippy"
-            ),
-            0
-        );
+        assert!(Svg::badge_with(
+            icons(),
+            true,
+            "clippy",
+            "failed",
+            "success",
+            "https://simpleicons.org/icons/rust.svg",
+        )
+        .save("badges", "clippy")
+        .is_ok());
     }
     #[test]
     pub fn china() {
-        assert_eq!(
-            Svg::new(900.0, "px", 600.0, "px", "0 0 900 600", "china")
+        assert!(
+            Svg::new(Length::px(900.0), Length::px(600.0), "0 0 900 600", "china")
                 .start()
                 .path()
                 .fill("#EE1C25")
@@ -76,157 +132,154 @@ mod tests {
                 .transform("translate(300,270) rotate(20.659808)")
                 .close()
                 .end()
-                .save("flags", "china"),
-            0
+                .save("flags", "china")
+                .is_ok()
         );
     }
     #[test]
     pub fn france() {
-        assert_eq!(
-            Svg::new(900.0, "px", 600.0, "px", "0 0 900 600", "france")
-                .start()
-                .rect()
-                .width(300.0, "px")
-                .height(600.0, "px")
-                .fill("#fff")
-                .x(300.0, "px")
-                .close()
-                .rect()
-                .width(300.0, "px")
-                .height(600.0, "px")
-                .fill("#ed2939")
-                .x(600.0, "px")
-                .close()
-                .rect()
-                .width(300.0, "px")
-                .height(600.0, "px")
-                .fill("#002395")
-                .close()
-                .end()
-                .save("flags", "france"),
-            0
-        );
+        assert!(Svg::new(
+            Length::px(900.0),
+            Length::px(600.0),
+            "0 0 900 600",
+            "france"
+        )
+        .start()
+        .rect()
+        .width(Length::px(300.0))
+        .height(Length::px(600.0))
+        .fill("#fff")
+        .x(Length::px(300.0))
+        .close()
+        .rect()
+        .width(Length::px(300.0))
+        .height(Length::px(600.0))
+        .fill("#ed2939")
+        .x(Length::px(600.0))
+        .close()
+        .rect()
+        .width(Length::px(300.0))
+        .height(Length::px(600.0))
+        .fill("#002395")
+        .close()
+        .end()
+        .save("flags", "france")
+        .is_ok());
     }
 
     #[test]
     pub fn italy() {
-        assert_eq!(
-            Svg::new(900.0, "px", 600.0, "px", "0 0 900 600", "italy")
+        assert!(
+            Svg::new(Length::px(900.0), Length::px(600.0), "0 0 900 600", "italy")
                 .start()
                 .rect()
-                .width(300.0, "px")
-                .height(600.0, "px")
+                .width(Length::px(300.0))
+                .height(Length::px(600.0))
                 .fill("#F1F2F1")
-                .x(300.0, "px")
+                .x(Length::px(300.0))
                 .close()
                 .rect()
-                .width(300.0, "px")
-                .height(600.0, "px")
+                .width(Length::px(300.0))
+                .height(Length::px(600.0))
                 .fill("#ce2b37")
-                .x(600.0, "px")
+                .x(Length::px(600.0))
                 .close()
                 .rect()
-                .width(300.0, "px")
-                .height(600.0, "px")
+                .width(Length::px(300.0))
+                .height(Length::px(600.0))
                 .fill("#009246")
                 .close()
                 .end()
-                .save("flags", "italy"),
-            0
+                .save("flags", "italy")
+                .is_ok()
         );
     }
 
     #[test]
     pub fn algeria() {
-        assert_eq!(Svg::new(    900.0,
-                                "px",
-                                600.0,
-                                "px",
-                                "0 0 900 600",
+        assert!(Svg::new(Length::px(900.0), Length::px(600.0), "0 0 900 600",
                                 "algeria")
                 .start()
                 .path().fill("#fff").d("M0 0h900v600H0z").close()
                 .path().fill("#063").d("M0 0h450v600H0z").close()
                 .path().fill("#d21034").d("M579.903811 225a150 150 0 1 0 0 150 120 120 0 1 1 0-150M585.676275 300 450 255.916106 533.852549 371.329239v-142.658277L450 344.083894z").close()
                 .end()
-                .save("flags", "algeria"),0);
+                .save("flags", "algeria").is_ok());
     }
 
     #[test]
     pub fn morocco() {
-        assert_eq!(
-            Svg::new(900.0, "px", 600.0, "px", "0 0 900 600", "morocco")
-                .start()
-                .path()
-                .fill("#c1272d")
-                .d("m0 0h90000v60000H0z")
-                .close()
-                .path()
-                .fill("none")
-                .d("m45000 17308 7460 22960-19531-14190h24142L37540 40268z")
-                .stroke("#006233")
-                .stroke_width(1426.0, "px")
-                .close()
-                .end()
-                .save("flags", "morocco"),
-            0
-        );
+        assert!(Svg::new(
+            Length::px(900.0),
+            Length::px(600.0),
+            "0 0 900 600",
+            "morocco"
+        )
+        .start()
+        .path()
+        .fill("#c1272d")
+        .d("m0 0h90000v60000H0z")
+        .close()
+        .path()
+        .fill("none")
+        .d("m45000 17308 7460 22960-19531-14190h24142L37540 40268z")
+        .stroke("#006233")
+        .stroke_width(Length::px(1426.0))
+        .close()
+        .end()
+        .save("flags", "morocco")
+        .is_ok());
     }
     #[test]
     pub fn greece() {
-        assert_eq!(
-            Svg::new(900.0, "px", 600.0, "px", "0 0 900 600", "greece")
-                .start()
-                .rect()
-                .width(27.0, "px")
-                .height(18.0, "px")
-                .fill("#0D5EAF")
-                .close()
-                .path()
-                .fill("none")
-                .d("M5,0V11 M0,5H10 M10,3H27 M10,7H27 M0,11H27 M0,15H27")
-                .stroke("#fff")
-                .stroke_width(2.0, "px")
-                .close()
-                .end()
-                .save("flags", "greece"),
-            0
-        );
+        assert!(Svg::new(
+            Length::px(900.0),
+            Length::px(600.0),
+            "0 0 900 600",
+            "greece"
+        )
+        .start()
+        .rect()
+        .width(Length::px(27.0))
+        .height(Length::px(18.0))
+        .fill("#0D5EAF")
+        .close()
+        .path()
+        .fill("none")
+        .d("M5,0V11 M0,5H10 M10,3H27 M10,7H27 M0,11H27 M0,15H27")
+        .stroke("#fff")
+        .stroke_width(Length::px(2.0))
+        .close()
+        .end()
+        .save("flags", "greece")
+        .is_ok());
     }
     #[test]
     pub fn arch() {
-        assert_eq!(Svg::new(    512.0,
-                                "px",
-                                512.0,
-                                "px",
-                                "0 0 512 512",
+        assert!(Svg::new(Length::px(512.0), Length::px(512.0), "0 0 512 512",
                                 "arch")
                 .start()
                 .rect()
-                .width(512.0,"px")
-                .height(512.0,"px")
+                .width(Length::px(512.0))
+                .height(Length::px(512.0))
                 .fill("#fff")
-                .rx(15.0,"px").close()
+                .rx(Length::px(15.0)).close()
                 .a()
                 .href("https://archlinux.org")
                 .target("_blank")
                 .close_tag()
                 .title("The arch distro")
-                .path().fill("none").d("M5,0V11 M0,5H10 M10,3H27 M10,7H27 M0,11H27 M0,15H27").stroke("#fff").stroke_width(2.0,"px").close()
+                .path().fill("none").d("M5,0V11 M0,5H10 M10,3H27 M10,7H27 M0,11H27 M0,15H27").stroke("#fff").stroke_width(Length::px(2.0)).close()
                 .path().fill("#1793d1").d("M256 72c-14 35-23 57-39 91 10 11 22 23 41 36-21-8-35-17-45-26-21 43-53 103-117 220 50-30 90-48 127-55-2-7-3-14-3-22v-1c1-33 18-58 38-56 20 1 36 29 35 62l-2 17c36 7 75 26 125 54l-27-50c-13-10-27-23-55-38 19 5 33 11 44 17-86-159-93-180-122-250z")
                 .close()
                 .close_a()
                 .end()
-            .save("distros", "arch"),0);
+            .save("distros", "arch").is_ok());
     }
 
     #[test]
     pub fn fedora() {
-        assert_eq!(Svg::new(    267.0,
-                                "px",
-                                267.0,
-                                "px",
-                                "0 0 267 267",
+        assert!(Svg::new(Length::px(267.0), Length::px(267.0), "0 0 267 267",
                                 "fedora")
                 .start()
                        .path()
@@ -239,7 +292,7 @@ mod tests {
                        .id("in")
                        .fill("none")
                        .stroke("#3c6eb4")
-                       .stroke_width(29.21,"px")
+                       .stroke_width(Length::px(29.21))
                        .close()
                        .u()
                        .transform("matrix(-1,0,0,-1,249.71151,284.2882)")
@@ -262,17 +315,17 @@ mod tests {
                        .fill("#3c6eb4")
                        .close()
                        .end()
-                .save("distros", "fedora"),0);
+                .save("distros", "fedora").is_ok());
     }
 
     #[test]
     pub fn animate() {
-        assert_eq!(
-            Svg::new(10.0, "px", 10.0, "px", "0 0 10 10", "rec")
+        assert!(
+            Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "rec")
                 .start()
                 .rect()
-                .width(10.0, "px")
-                .height(10.0, "px")
+                .width(Length::px(10.0))
+                .height(Length::px(10.0))
                 .close_tag()
                 .animate()
                 .attribute_name("rx")
@@ -282,55 +335,1735 @@ mod tests {
                 .close()
                 .close_rect()
                 .end()
-                .save("animates", "rectangle"),
-            0
+                .save("animates", "rectangle")
+                .is_ok()
         );
     }
     #[test]
     pub fn ellipse() {
-        assert_eq!(
-            Svg::new(200.0, "px", 100.0, "px", "0 0 200 100", "ellipse")
-                .start()
-                .ellipse()
-                .cx(100.0, "px")
-                .cy(50.0, "px")
-                .rx(100.0, "px")
-                .ry(50.0, "px")
-                .close()
-                .end()
-                .save("animates", "ellipse"),
-            0
-        );
+        assert!(Svg::new(
+            Length::px(200.0),
+            Length::px(100.0),
+            "0 0 200 100",
+            "ellipse"
+        )
+        .start()
+        .ellipse()
+        .cx(Length::px(100.0))
+        .cy(Length::px(50.0))
+        .rx(Length::px(100.0))
+        .ry(Length::px(50.0))
+        .close()
+        .end()
+        .save("animates", "ellipse")
+        .is_ok());
     }
 
     #[test]
     pub fn zuu() {
-        assert_eq!(
-            Svg::new(10.0, "px", 10.0, "px", "0 0 10 10", "zuu")
+        assert!(
+            Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "zuu")
+                .fetcher(icons())
                 .start()
                 .image("https://simpleicons.org/icons/grunt.svg")
-                .width(14.0, "px")
-                .height(14.0, "px")
+                .width(Length::px(14.0))
+                .height(Length::px(14.0))
                 .close()
                 .text()
-                .x(495.26276, "px")
-                .y(175.0, "px")
+                .x(Length::px(495.26276))
+                .y(Length::px(175.0))
                 .transform("scale(0.1)")
                 .fill("#222")
                 .close_tag()
                 .content("CLIPPY")
                 .close_text()
                 .text()
-                .x(1231.3833, "px")
-                .y(175.0, "px")
+                .x(Length::px(1231.3833))
+                .y(Length::px(175.0))
                 .transform("scale(0.1)")
                 .fill("#222")
                 .close_tag()
                 .content("SUCCESS")
                 .close_text()
                 .end()
-                .save("graphics", "zuu"),
-            0
+                .save("graphics", "zuu")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    pub fn tree() {
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "tree");
+        svg.start()
+            .g()
+            .id("group")
+            .close_tag()
+            .rect()
+            .id("square")
+            .width(Length::px(10.0))
+            .fill("#fff")
+            .fill("#000")
+            .close()
+            .close_group()
+            .end();
+        let root = svg.root().expect("missing root");
+        assert_eq!(root.tag(), "svg");
+        let square = root.find("square").expect("missing rect");
+        assert_eq!(square.tag(), "rect");
+        assert_eq!(square.attribute("fill"), Some("#000000"));
+        assert_eq!(square.attributes().count(), 3);
+        svg.root_mut()
+            .and_then(|r| r.find_mut("square"))
+            .expect("missing rect")
+            .set_attribute("fill", "#bc002d");
+        assert_eq!(
+            svg.root()
+                .and_then(|r| r.find("group"))
+                .and_then(|g| g.elements().next())
+                .and_then(|r| r.attribute("fill")),
+            Some("#bc002d")
+        );
+        svg.circle().id("after").r(1.0).close_circle();
+        assert_eq!(
+            svg.root()
+                .and_then(|r| r.elements().last())
+                .and_then(|c| c.attribute("id")),
+            Some("after")
+        );
+        assert!(svg.save("graphics", "tree").is_ok());
+        let mut early = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "early");
+        early.fill("#000").start().end();
+        assert!(matches!(
+            early.save("graphics", "early"),
+            Err(SvgError::NotStarted(name)) if name == "fill"
+        ));
+    }
+
+    #[test]
+    pub fn invalid() {
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "invalid");
+        svg.start().text().close_tag().raw("<b>").close_text().end();
+        match svg.save("graphics", "invalid") {
+            Err(SvgError::Validation { line, message, .. }) => {
+                assert_eq!(line, 2);
+                assert!(!message.is_empty());
+            }
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    pub fn escape() {
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "escape");
+        svg.start()
+            .text()
+            .class("say \"hi\"")
+            .close_tag()
+            .content("Fish & <Chips>")
+            .close_text()
+            .end();
+        let file = svg.save("graphics", "escape").expect("invalid svg");
+        let xml = std::fs::read_to_string(file).expect("missing svg");
+        assert!(xml.contains("class=\"say &quot;hi&quot;\""));
+        assert!(xml.contains("Fish &amp; &lt;Chips&gt;"));
+    }
+
+    #[test]
+    pub fn typed() {
+        let mut typed = Svg::new(Length::px(900.0), Length::px(600.0), "0 0 900 600", "japan");
+        typed
+            .start()
+            .build()
+            .rect()
+            .width(Length::px(900.0))
+            .height(Length::px(600.0))
+            .fill("#fff")
+            .close()
+            .g()
+            .id("sun")
+            .circle()
+            .cx(Length::px(450.0))
+            .cy(Length::px(300.0))
+            .r(Length::px(180.0))
+            .fill("#bc002d")
+            .title()
+            .content("Sun")
+            .close()
+            .close()
+            .close()
+            .done()
+            .end();
+        let mut string = Svg::new(Length::px(900.0), Length::px(600.0), "0 0 900 600", "japan");
+        string
+            .start()
+            .rect()
+            .width(Length::px(900.0))
+            .height(Length::px(600.0))
+            .fill("#fff")
+            .close()
+            .g()
+            .id("sun")
+            .close_tag()
+            .circle()
+            .cx(Length::px(450.0))
+            .cy(Length::px(300.0))
+            .r(Length::px(180.0))
+            .fill("#bc002d")
+            .close_tag()
+            .title("Sun")
+            .close_circle()
+            .close_group()
+            .end();
+        assert_eq!(typed.root(), string.root());
+        assert!(typed.save("flags", "typed").is_ok());
+    }
+
+    #[test]
+    pub fn well_formed() {
+        assert!(validate("<?xml version=\"1.0\"?>\n<svg xmlns:xlink=\"x\"><use xlink:href=\"#a\"/><!-- ok --><text>&amp;&#38;</text></svg>").is_empty());
+        let diagnostics = validate("<svg a=\"1\" a='2' b=3><x:use/></svg>\ntext");
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "attribute a redefined",
+                "value of the attribute b must be quoted",
+                "namespace prefix x of x:use is not defined",
+                "content outside the root element",
+            ]
+        );
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 12));
+        assert_eq!((diagnostics[3].line, diagnostics[3].column), (2, 1));
+        assert_eq!(
+            validate("<svg><g></svg>")[0].message,
+            "opening and ending tag mismatch: g and svg"
+        );
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "malformed");
+        svg.start().raw("<g>").end();
+        assert!(svg.save("graphics", "malformed").is_err());
+        assert!(!std::path::Path::new("graphics/malformed.svg").exists());
+    }
+
+    #[test]
+    pub fn embed() {
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"M"), "TQ==");
+        assert_eq!(
+            MimeType::sniff(b"\x89PNG\r\n\x1a\n\0\0"),
+            Some(MimeType::Png)
+        );
+        assert_eq!(
+            MimeType::sniff(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some(MimeType::Webp)
+        );
+        assert_eq!(
+            MimeType::sniff(b"<?xml version=\"1.0\"?><svg/>"),
+            Some(MimeType::Svg)
+        );
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "embed");
+        svg.start()
+            .image_from_bytes(b"GIF89a")
+            .id("gif")
+            .close()
+            .end();
+        assert_eq!(
+            svg.root()
+                .and_then(|r| r.find("gif"))
+                .and_then(|i| i.attribute("xlink:href")),
+            Some("data:image/gif;base64,R0lGODlh")
+        );
+        let mut unknown = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "unknown");
+        unknown.start().image_from_bytes(b"text").close().end();
+        assert!(matches!(
+            unknown.save("graphics", "unknown"),
+            Err(SvgError::UnsupportedImage)
+        ));
+        assert!(matches!(
+            unknown.clone().save("graphics", "unknown"),
+            Err(SvgError::UnsupportedImage)
+        ));
+        let mut missing = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "missing");
+        missing.start().image_from_path("missing.png").close().end();
+        assert!(matches!(
+            missing.save("graphics", "missing"),
+            Err(SvgError::Io(_))
+        ));
+        assert!(matches!(
+            missing.save("graphics", "missing"),
+            Err(SvgError::Io(_))
+        ));
+    }
+
+    #[test]
+    pub fn fetch() {
+        let dir = std::env::temp_dir().join("svg-creator-fetch");
+        std::fs::create_dir_all(dir.join("simpleicons.org/icons"))
+            .expect("failed to create the cache");
+        std::fs::write(dir.join("simpleicons.org/icons/rust.svg"), ICON)
+            .expect("failed to fill the cache");
+        std::fs::write(dir.join("grunt.svg"), ICON).expect("failed to fill the cache");
+        let cache = OfflineFetcher::new().cache(dir.as_path());
+        assert!(cache
+            .fetch("https://simpleicons.org/icons/rust.svg")
+            .is_ok());
+        assert!(cache.fetch("https://example.org/grunt.svg").is_ok());
+        assert!(matches!(
+            cache.fetch("https://simpleicons.org/icons/go.svg"),
+            Err(SvgError::ResourceNotFound(_))
+        ));
+        assert!(FileFetcher::new(dir.as_path())
+            .fetch("file://grunt.svg")
+            .is_ok());
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "fetch");
+        svg.fetcher(|uri: &str| Err(SvgError::ResourceNotFound(uri.to_string())))
+            .start()
+            .image("offline.png")
+            .close()
+            .end();
+        assert!(matches!(
+            svg.save("graphics", "fetch"),
+            Err(SvgError::ResourceNotFound(_))
+        ));
+    }
+
+    #[test]
+    pub fn output() {
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "output");
+        svg.start()
+            .circle()
+            .cx(Length::px(5.0))
+            .cy(Length::px(5.0))
+            .r(Length::px(5.0))
+            .close()
+            .end();
+        let expected = "<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" width=\"10px\" height=\"10px\" viewBox=\"0 0 10 10\" role=\"img\" id=\"output\"><circle cx=\"5px\" cy=\"5px\" r=\"5px\"/></svg>";
+        assert_eq!(svg.to_string(), expected);
+        assert_eq!(
+            svg.root()
+                .and_then(|r| r.elements().next())
+                .map(ToString::to_string),
+            Some("<circle cx=\"5px\" cy=\"5px\" r=\"5px\"/>".to_string())
+        );
+        let shared: &Svg = &svg;
+        let mut out = Vec::new();
+        assert!(shared.write_to(&mut out).is_ok());
+        assert_eq!(out, expected.as_bytes());
+        assert_eq!(svg.into_bytes().ok(), Some(expected.as_bytes().to_vec()));
+        let mut failed = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "failed");
+        failed.start().image_from_bytes(b"text").close().end();
+        assert!(failed.write_to(&mut Vec::new()).is_err());
+        assert!(failed.write_to(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    pub fn formats() {
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10.0 10", "formats");
+        svg.start()
+            .g()
+            .close_tag()
+            .path()
+            .d("M 0.50 0 L -0.5 10 , 10 10 z M 1e2 0")
+            .transform("translate(0.5, 0) rotate(45)")
+            .fill("#000000")
+            .close()
+            .close_group()
+            .title("Formats")
+            .end();
+        svg.format(Format::Pretty { indent: 2 });
+        assert_eq!(
+            svg.to_string(),
+            "<?xml version=\"1.0\"?>
+<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" width=\"10px\" height=\"10px\" viewBox=\"0 0 10 10\" role=\"img\" id=\"formats\">
+  <g>
+    <path d=\"M 0.50 0 L -0.5 10 , 10 10 z M 1e2 0\" transform=\"translate(0.5, 0) rotate(45)\" fill=\"#000000\"/>
+  </g>
+  <title>Formats</title>
+</svg>
+"
+        );
+        svg.format(Format::Minified);
+        assert_eq!(
+            svg.to_string(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" width=\"10px\" height=\"10px\" viewBox=\"0 0 10 10\" role=\"img\" id=\"formats\"><g><path d=\"M.5 0L-.5 10 10 10zM1e2 0\" transform=\"translate(.5 0) rotate(45)\" fill=\"#000000\"/></g><title>Formats</title></svg>"
+        );
+        assert!(svg.save("graphics", "formats").is_ok());
+    }
+
+    #[test]
+    pub fn lengths() {
+        assert_eq!(Length::px(900.0).to_string(), "900px");
+        assert_eq!(Length::from(1.5).to_string(), "1.5");
+        assert_eq!(Length::percent(50.0).to_string(), "50%");
+        assert_eq!("12.5mm".parse::<Length>().ok(), Some(Length::mm(12.5)));
+        assert_eq!("3".parse::<Length>().ok(), Some(Length::unitless(3.0)));
+        assert!(matches!(
+            "10pz".parse::<Length>(),
+            Err(SvgError::InvalidLength(_))
+        ));
+        assert_eq!(Length::inches(1.0).to(Unit::Px), Some(Length::px(96.0)));
+        assert_eq!(Length::pt(72.0).to(Unit::In), Some(Length::inches(1.0)));
+        assert_eq!(Length::pc(1.0).to_px(), Some(16.0));
+        assert_eq!(Length::em(1.0).to(Unit::Px), None);
+        let cm = Length::mm(25.4).to(Unit::Cm).map(|l| l.value);
+        assert!(cm.is_some_and(|v| (v - 2.54).abs() < 1e-9));
+        let mut svg = Svg::new(Length::mm(90.0), Length::mm(60.0), "0 0 900 600", "lengths");
+        svg.start()
+            .rect()
+            .width(Length::percent(100.0))
+            .height(600.0)
+            .stroke_width((2.0, Unit::Pt))
+            .close()
+            .end();
+        assert!(svg.to_string().contains(
+            "width=\"90mm\" height=\"60mm\" viewBox=\"0 0 900 600\" role=\"img\" id=\"lengths\"><rect width=\"100%\" height=\"600\" stroke-width=\"2pt\"/>"
+        ));
+    }
+
+    #[test]
+    pub fn colors() {
+        assert_eq!("#fff".parse::<Color>().ok(), Some(Color::WHITE));
+        assert_eq!(
+            "#bc002d".parse::<Color>().ok(),
+            Some(Color::hex(0x00bc_002d))
+        );
+        assert_eq!(
+            "#f008".parse::<Color>().ok(),
+            Some(Color::rgba(255, 0, 0, 136.0 / 255.0))
+        );
+        assert_eq!(
+            "#00000080".parse::<Color>().map(Color::to_hex).ok(),
+            Some("#00000080".to_string())
+        );
+        assert_eq!(
+            "rgb(255, 0, 0)".parse::<Color>().ok(),
+            Some(Color::rgb(255, 0, 0))
+        );
+        assert_eq!(
+            "rgba(100%, 0%, 0%, 0.5)".parse::<Color>().ok(),
+            Some(Color::rgba(255, 0, 0, 0.5))
+        );
+        assert_eq!(
+            "hsl(120, 100%, 50%)".parse::<Color>().ok(),
+            Some(Color::rgb(0, 255, 0))
+        );
+        assert_eq!(
+            "RebeccaPurple".parse::<Color>().ok(),
+            Some(Color::hex(0x0066_3399))
+        );
+        assert!(matches!(
+            "#ggg".parse::<Color>(),
+            Err(SvgError::InvalidColor(_))
+        ));
+        let (h, s, l) = Color::hex(0x0066_3399).to_hsl();
+        assert_eq!(
+            (h.round(), (s * 100.0).round(), (l * 100.0).round()),
+            (270.0, 50.0, 40.0)
+        );
+        assert_eq!(Color::hsl(h, s, l), Color::hex(0x0066_3399));
+        assert_eq!(
+            Color::rgb(128, 128, 128).lighten(0.5),
+            Color::rgb(255, 255, 255)
+        );
+        assert_eq!(Color::rgb(128, 128, 128).darken(1.0), Color::BLACK);
+        assert_eq!(
+            Color::BLACK.mix(Color::WHITE, 0.5),
+            Color::rgb(128, 128, 128)
+        );
+        assert_eq!(Color::BLACK.alpha(0.25).to_string(), "#00000040");
+        assert_eq!(
+            "url(#sky)".parse::<Paint>().ok(),
+            Some(Paint::Url("sky".to_string()))
+        );
+        assert_eq!(Paint::CurrentColor.to_string(), "currentColor");
+        assert_eq!("inherit".parse::<Paint>().ok(), Some(Paint::Inherit));
+        assert_eq!(
+            "currentcolor".parse::<Paint>().ok(),
+            Some(Paint::CurrentColor)
+        );
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "colors");
+        svg.start()
+            .rect()
+            .fill(Color::hex(0x00bc_002d))
+            .stroke(Paint::None)
+            .close()
+            .circle()
+            .fill("#fff")
+            .stroke(Color::BLACK.alpha(0.5))
+            .close()
+            .path()
+            .fill("#0008")
+            .fill("inherit")
+            .close()
+            .end();
+        assert!(svg.to_string().contains(
+            "<rect fill=\"#bc002d\" stroke=\"none\"/><circle fill=\"#ffffff\" stroke=\"#000000\" stroke-opacity=\".5\"/><path fill=\"inherit\"/>"
+        ));
+        svg.start().rect().fill("#bc002z").close().end();
+        assert!(matches!(
+            svg.save("graphics", "colors"),
+            Err(SvgError::InvalidColor(_))
+        ));
+    }
+
+    #[test]
+    pub fn paths() {
+        assert_eq!(
+            PathBuilder::new()
+                .move_to(0.0, 0.0)
+                .h(900.0)
+                .v(600.0)
+                .h(0.0)
+                .close()
+                .to_string(),
+            "M0 0H900V600H0Z"
+        );
+        assert_eq!(
+            PathBuilder::new()
+                .move_by(10.0, 10.0)
+                .line_by(-5.5, 0.5)
+                .line_by(0.5, 0.25)
+                .cubic_to((1.0, 2.0), (3.0, 4.0), (5.0, 6.0))
+                .smooth_cubic_by((1.0, 1.0), (2.0, -2.0))
+                .quad_to((1.0, 1.0), (2.0, 2.0))
+                .smooth_quad_to(3.0, 3.0)
+                .arc_by((5.0, 5.0), 0.0, true, false, (10.0, 0.0))
+                .close()
+                .to_string(),
+            "m10 10l-5.5.5.5.25C1 2 3 4 5 6s1 1 2-2Q1 1 2 2T3 3a5 5 0 1 0 10 0Z"
+        );
+        assert_eq!(
+            PathBuilder::new()
+                .polygon((5.0, 5.0), 5.0, 4, 0.0)
+                .to_string(),
+            "M5 0L10 5 5 10 0 5Z"
+        );
+        assert_eq!(
+            PathBuilder::new()
+                .rounded_rect((0.0, 0.0), (10.0, 10.0), (2.0, 8.0))
+                .to_string(),
+            "M2 0H8A2 5 0 0 1 10 5V5A2 5 0 0 1 8 10H2A2 5 0 0 1 0 5V5A2 5 0 0 1 2 0Z"
+        );
+        assert_eq!(
+            PathBuilder::new()
+                .rounded_rect((10.0, 10.0), (-10.0, -10.0), (2.0, 8.0))
+                .to_string(),
+            "M2 0H8A2 5 0 0 1 10 5V5A2 5 0 0 1 8 10H2A2 5 0 0 1 0 5V5A2 5 0 0 1 2 0Z"
+        );
+        assert_eq!(
+            PathBuilder::new()
+                .rounded_rect((0.0, 0.0), (4.0, 4.0), (f64::NAN, 1.0))
+                .to_string(),
+            "M0 0H4V4H0Z"
+        );
+        let star = PathBuilder::new()
+            .precision(3)
+            .star((0.0, 0.0), 30.0, 12.0, 5, 0.0);
+        assert_eq!(star.commands().len(), 11);
+        assert!(star
+            .to_string()
+            .starts_with("M0-30L7.053-9.708 28.532-9.271"));
+        assert!(
+            Svg::new(Length::px(900.0), Length::px(600.0), "0 0 900 600", "stars")
+                .start()
+                .path()
+                .fill("#EE1C25")
+                .d(PathBuilder::new().rounded_rect((0.0, 0.0), (900.0, 600.0), (0.0, 0.0)))
+                .close()
+                .path()
+                .fill("#FF0")
+                .d(PathBuilder::new()
+                    .star((150.0, 150.0), 90.0, 34.4, 5, 0.0)
+                    .star((300.0, 60.0), 30.0, 11.5, 5, 23.036243))
+                .close()
+                .end()
+                .save("flags", "stars")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    pub fn parsing() {
+        assert_eq!(
+            parse("M0,-30 17.6 24.2-28.5-9.2zm1 1").ok(),
+            Some(vec![
+                Command::MoveTo {
+                    relative: false,
+                    x: 0.0,
+                    y: -30.0
+                },
+                Command::LineTo {
+                    relative: false,
+                    x: 17.6,
+                    y: 24.2
+                },
+                Command::LineTo {
+                    relative: false,
+                    x: -28.5,
+                    y: -9.2
+                },
+                Command::Close,
+                Command::MoveTo {
+                    relative: true,
+                    x: 1.0,
+                    y: 1.0
+                },
+            ])
+        );
+        assert_eq!(
+            parse("M10 10a5 5 30 1110 0").ok(),
+            Some(vec![
+                Command::MoveTo {
+                    relative: false,
+                    x: 10.0,
+                    y: 10.0
+                },
+                Command::ArcTo {
+                    relative: true,
+                    rx: 5.0,
+                    ry: 5.0,
+                    rotation: 30.0,
+                    large_arc: true,
+                    sweep: true,
+                    x: 10.0,
+                    y: 0.0
+                },
+            ])
+        );
+        for d in [
+            "M0 0h900v600H0",
+            "M579.903811 225a150 150 0 1 0 0 150 120 120 0 1 1 0-150M585.676275 300 450 255.916106 533.852549 371.329239v-142.658277L450 344.083894z",
+            "m45000 17308 7460 22960-19531-14190h24142L37540 40268z",
+            "M5,0V11 M0,5H10 M10,3H27 M10,7H27 M0,11H27 M0,15H27",
+            "M 0.50 0 L -0.5 10 , 10 10 z M 1e2 0",
+        ] {
+            let path = d.parse::<PathBuilder>().expect("invalid path");
+            assert_eq!(parse(path.to_string().as_str()).ok().as_deref(), Some(path.commands()));
+        }
+        for (d, offset) in [
+            ("L0 0", 0),
+            ("M0 0 L10", 8),
+            ("M0 0 X1 1", 5),
+            ("M0 0 A5 5 0 2 0 1 1", 12),
+            ("M0 0z 1 1", 6),
+            ("M0 0 L1 1,,2 2", 10),
+        ] {
+            assert!(
+                matches!(parse(d), Err(SvgError::InvalidPath { offset: o, .. }) if o == offset),
+                "{d}"
+            );
+        }
+        let mut svg = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "parsing");
+        svg.start().path().d("M0 0L10").close().end();
+        assert!(matches!(
+            svg.save("graphics", "parsing"),
+            Err(SvgError::InvalidPath { offset: 7, .. })
+        ));
+    }
+
+    #[test]
+    pub fn normalization() {
+        let path = |d: &str| d.parse::<PathBuilder>().expect("invalid path").precision(3);
+        assert_eq!(
+            path("m10 10l5 5h5v-5zm1 1").to_absolute().to_string(),
+            "M10 10L15 15H20V10ZM11 11"
+        );
+        assert_eq!(
+            path("M10 10L15 15H20V10ZM11 11").to_relative().to_string(),
+            "m10 10l5 5h5v-5Zm1 1"
+        );
+        assert_eq!(
+            path("M0 0c0 10 10 10 10 0s10-10 10 0H0Q5 5 10 0T20 0")
+                .expand_shorthands()
+                .to_string(),
+            "M0 0C0 10 10 10 10 0 10-10 20-10 20 0L0 0Q5 5 10 0 15-5 20 0"
+        );
+        let arc = path("M0 0A10 10 0 0 1 20 0").arcs_to_cubics();
+        assert_eq!(arc.commands().len(), 3);
+        assert!(matches!(
+            arc.commands()[1],
+            Command::CubicTo { x, y, .. } if (x - 10.0).abs() < 1e-9 && (y + 10.0).abs() < 1e-9
+        ));
+        assert!(matches!(
+            arc.commands()[2],
+            Command::CubicTo { x, y, .. } if (x - 20.0).abs() < 1e-9 && y.abs() < 1e-9
+        ));
+        assert_eq!(
+            path("M0 0a5 5 0 0 0 0 0a0 5 0 0 0 10 10")
+                .arcs_to_cubics()
+                .to_string(),
+            "M0 0L10 10"
+        );
+        assert_eq!(
+            path("M100 100L101 101L102 102V5").shortest().to_string(),
+            "M100 100l1 1 1 1V5"
+        );
+        let canonical = PathBuilder::new()
+            .rounded_rect((0.0, 0.0), (10.0, 10.0), (2.0, 2.0))
+            .quad_by((1.0, 1.0), (2.0, 0.0))
+            .smooth_quad_by(2.0, 0.0)
+            .normalize();
+        assert!(canonical
+            .commands()
+            .iter()
+            .all(|c| matches!(c.letter(), 'M' | 'L' | 'C' | 'Z')));
+        assert_eq!(canonical.to_absolute(), canonical);
+    }
+
+    #[test]
+    pub fn transforms() {
+        let close =
+            |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9;
+        let star = Transform::new().translate(300.0, 60.0).rotate(23.036243);
+        assert_eq!(star.to_string(), "translate(300,60) rotate(23.036243)");
+        assert_eq!(
+            "translate(300,60) rotate(23.036243)"
+                .parse::<Transform>()
+                .ok(),
+            Some(star.clone())
+        );
+        let flip = "matrix(-1,0,0,-1,249.71151,284.2882)"
+            .parse::<Transform>()
+            .expect("invalid transform");
+        assert_eq!(
+            flip.to_matrix(),
+            Matrix::new(-1.0, 0.0, 0.0, -1.0, 249.71151, 284.2882)
+        );
+        assert!(close(flip.apply((0.0, 0.0)), (249.71151, 284.2882)));
+        assert!(close(
+            Transform::new()
+                .rotate_around(90.0, (10.0, 10.0))
+                .apply((20.0, 10.0)),
+            (10.0, 20.0)
+        ));
+        assert!(close(
+            Transform::new().scale(2.0, 3.0).apply((1.0, 1.0)),
+            (2.0, 3.0)
+        ));
+        assert!(close(
+            Transform::new().skew_x(45.0).apply((0.0, 1.0)),
+            (1.0, 1.0)
+        ));
+        assert!(close(
+            Transform::new().skew_y(45.0).apply((1.0, 0.0)),
+            (1.0, 1.0)
+        ));
+        let composed = Transform::new()
+            .translate(10.0, 0.0)
+            .then(&Transform::new().scale(2.0, 2.0));
+        assert!(close(composed.apply((1.0, 1.0)), (12.0, 2.0)));
+        assert_eq!(composed.collapse().to_string(), "matrix(2,0,0,2,10,0)");
+        let inverse = star.inverse().expect("not invertible");
+        assert!(close(inverse.apply(star.apply((3.0, 4.0))), (3.0, 4.0)));
+        assert!(star.clone().then(&inverse).is_identity());
+        assert!(Transform::new().scale(0.0, 1.0).inverse().is_none());
+        assert_eq!(
+            "rotate(45 5 5)scale(2),skewX(-10)"
+                .parse::<Transform>()
+                .map(|t| t.to_string())
+                .ok(),
+            Some("rotate(45,5,5) scale(2) skewX(-10)".to_string())
+        );
+        for invalid in ["scale(1,2,3)", "translate(1", "spin(3)", "rotate(1,)"] {
+            assert!(matches!(
+                invalid.parse::<Transform>(),
+                Err(SvgError::InvalidTransform(_))
+            ));
+        }
+        let mut svg = Svg::new(
+            Length::px(10.0),
+            Length::px(10.0),
+            "0 0 10 10",
+            "transforms",
+        );
+        svg.start()
+            .g()
+            .transform(star)
+            .close_tag()
+            .close_group()
+            .end();
+        assert!(svg
+            .to_string()
+            .contains("<g transform=\"translate(300,60) rotate(23.036243)\"/>"));
+        svg.start()
+            .g()
+            .transform("rotate(45")
+            .close_tag()
+            .close_group()
+            .end();
+        assert!(matches!(
+            svg.save("graphics", "transforms"),
+            Err(SvgError::InvalidTransform(_))
+        ));
+    }
+
+    #[test]
+    pub fn flatten() {
+        let mut svg = Svg::new(
+            Length::px(900.0),
+            Length::px(600.0),
+            "0 0 900 600",
+            "flatten",
+        );
+        svg.start()
+            .g()
+            .transform("translate(150,150) scale(3)")
+            .stroke_width(Length::px(2.0))
+            .close_tag()
+            .rect()
+            .width(Length::px(10.0))
+            .height(Length::px(20.0))
+            .rx(Length::px(2.0))
+            .stroke_width(Length::px(1.0))
+            .close()
+            .circle()
+            .r(Length::px(5.0))
+            .transform("rotate(30)")
+            .close()
+            .ellipse()
+            .rx(Length::px(5.0))
+            .ry(Length::px(2.0))
+            .close()
+            .line(0.0, 0.0, 0.0, 50.0, "#000")
+            .transform("rotate(90)")
+            .close()
+            .close_group()
+            .g()
+            .transform("rotate(45)")
+            .close_tag()
+            .rect()
+            .width(Length::px(10.0))
+            .height(Length::px(10.0))
+            .id("diamond")
+            .close()
+            .close_group()
+            .g()
+            .transform("translate(150,150) scale(3)")
+            .close_tag()
+            .path()
+            .d("M0,-30 17.63355,24.27051 -28.53171,-9.27051H28.53171L-17.63355,24.27051")
+            .id("s")
+            .close()
+            .close_group()
+            .u()
+            .href("s")
+            .transform("translate(300,60) rotate(23.036243)")
+            .close()
+            .end();
+        svg.flatten_transforms();
+        let root = svg.root().expect("missing root");
+        let group = root.elements().next().expect("missing group");
+        assert_eq!(group.attribute("transform"), None);
+        assert_eq!(group.attribute("stroke-width"), None);
+        let star = root.elements().nth(2).expect("missing group");
+        assert_eq!(
+            star.attribute("transform"),
+            Some("translate(150,150) scale(3)")
+        );
+        assert_eq!(
+            root.find("s").and_then(|s| s.attribute("d")),
+            Some("M0,-30 17.63355,24.27051 -28.53171,-9.27051H28.53171L-17.63355,24.27051")
+        );
+        let shapes: Vec<_> = group.elements().collect();
+        assert_eq!(
+            shapes[0].attributes().collect::<Vec<_>>(),
+            [
+                ("width", "30"),
+                ("height", "60"),
+                ("rx", "6"),
+                ("stroke-width", "3"),
+                ("x", "150"),
+                ("y", "150"),
+                ("ry", "6")
+            ]
+        );
+        assert_eq!(shapes[1].attribute("r"), Some("15"));
+        assert_eq!(shapes[1].attribute("cx"), Some("150"));
+        assert_eq!(shapes[1].attribute("stroke-width"), Some("6"));
+        assert_eq!(shapes[2].attribute("rx"), Some("15"));
+        assert_eq!(shapes[3].attribute("x2"), Some("0"));
+        assert_eq!(shapes[3].attribute("y2"), Some("150"));
+        let diamond = root.find("diamond").expect("missing rect");
+        assert_eq!(diamond.tag(), "path");
+        assert_eq!(diamond.attribute("width"), None);
+        assert!(diamond
+            .attribute("d")
+            .is_some_and(|d| d.starts_with("M0 0L7.071068 7.071068")));
+        assert_eq!(
+            root.elements()
+                .last()
+                .and_then(|u| u.attribute("transform")),
+            Some("matrix(.920258,.391313,-.391313,.920258,300,60)")
+        );
+        assert!(svg.save("graphics", "flatten").is_ok());
+        let mut strokes = Svg::new(Length::px(90.0), Length::px(90.0), "0 0 90 90", "strokes");
+        strokes
+            .start()
+            .g()
+            .transform("scale(3)")
+            .close_tag()
+            .line(0.0, 0.0, 10.0, 0.0, "#000")
+            .close()
+            .close_group()
+            .g()
+            .stroke_width(Length::px(2.0))
+            .close_tag()
+            .g()
+            .transform("scale(3)")
+            .close_tag()
+            .line(0.0, 10.0, 10.0, 10.0, "#000")
+            .close()
+            .close_group()
+            .close_group()
+            .end();
+        strokes.flatten_transforms();
+        let xml = strokes.to_string();
+        assert!(xml.contains("<g><line x1=\"0\" y1=\"0\" x2=\"30\" y2=\"0\" stroke=\"#000000\" stroke-width=\"3\"/></g>"));
+        assert!(xml.contains("<g stroke-width=\"2px\"><g><line x1=\"0\" y1=\"30\" x2=\"30\" y2=\"30\" stroke=\"#000000\" stroke-width=\"6\"/></g></g>"));
+    }
+
+    #[test]
+    pub fn bounds() {
+        let near = |a: Option<BoundingBox>, b: BoundingBox| {
+            a.is_some_and(|a| {
+                [
+                    a.min_x - b.min_x,
+                    a.min_y - b.min_y,
+                    a.max_x - b.max_x,
+                    a.max_y - b.max_y,
+                ]
+                .iter()
+                .all(|d| d.abs() < 1e-3)
+            })
+        };
+        let curve =
+            PathBuilder::new()
+                .move_to(0.0, 0.0)
+                .cubic_to((0.0, 10.0), (10.0, 10.0), (10.0, 0.0));
+        assert!(near(
+            curve.bounding_box(),
+            BoundingBox::new(0.0, 0.0, 10.0, 7.5)
+        ));
+        let arc = PathBuilder::new().move_to(0.0, 0.0).arc_to(
+            (10.0, 10.0),
+            0.0,
+            false,
+            true,
+            (20.0, 0.0),
+        );
+        assert!(near(
+            arc.bounding_box(),
+            BoundingBox::new(0.0, -10.0, 20.0, 0.0)
+        ));
+        let (corner, extent) = (200.0f64.sqrt(), 250.0f64.sqrt());
+        let ellipse = PathBuilder::new()
+            .move_to(-corner, -corner)
+            .arc_to((20.0, 10.0), 45.0, false, true, (corner, corner))
+            .arc_to((20.0, 10.0), 45.0, false, true, (-corner, -corner));
+        assert!(ellipse.bounding_box().is_some_and(|b| {
+            [b.min_x, b.min_y, b.max_x, b.max_y]
+                .iter()
+                .all(|v| (v.abs() - extent).abs() < 1e-9)
+        }));
+        let mut svg = Svg::new(
+            Length::px(100.0),
+            Length::px(100.0),
+            "0 0 100 100",
+            "bounds",
+        );
+        svg.start()
+            .rect()
+            .x(Length::px(10.0))
+            .y(Length::px(10.0))
+            .width(Length::px(20.0))
+            .height(Length::px(10.0))
+            .id("rect")
+            .close()
+            .g()
+            .transform("translate(100,0)")
+            .close_tag()
+            .circle()
+            .r(Length::px(10.0))
+            .stroke("#000")
+            .stroke_width(Length::px(2.0))
+            .transform("scale(2,1)")
+            .id("circle")
+            .close()
+            .close_group()
+            .path()
+            .d("M0 50 10 60 0 70Z")
+            .transform("rotate(90)")
+            .close()
+            .line(0.0, 100.0, 10.0, 100.0, "#000")
+            .id("line")
+            .close()
+            .end();
+        assert!(near(
+            svg.element_bounding_box("rect", false),
+            BoundingBox::new(10.0, 10.0, 30.0, 20.0)
+        ));
+        assert!(near(
+            svg.element_bounding_box("circle", false),
+            BoundingBox::new(80.0, -10.0, 120.0, 10.0)
+        ));
+        assert!(near(
+            svg.element_bounding_box("circle", true),
+            BoundingBox::new(78.585, -11.414, 121.414, 11.414)
+        ));
+        assert!(near(
+            svg.bounding_box(false),
+            BoundingBox::new(-70.0, -10.0, 120.0, 100.0)
+        ));
+        assert!(near(
+            svg.bounding_box(true),
+            BoundingBox::new(-70.0, -11.414, 121.414, 100.5)
+        ));
+        svg.fit_view_box(1.0);
+        assert_eq!(
+            svg.root().and_then(|r| r.attribute("viewBox")),
+            Some("-71 -12.414214 193.414214 113.914214")
+        );
+        assert!(svg.save("graphics", "bounds").is_ok());
+    }
+
+    #[test]
+    pub fn viewports() {
+        let close =
+            |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9;
+        let flag = ViewBox::new(0.0, 0.0, 900.0, 600.0);
+        assert_eq!("0,0 900 600".parse::<ViewBox>().ok(), Some(flag));
+        assert_eq!(flag.to_string(), "0 0 900 600");
+        assert!(matches!(
+            "0 0 -1 10".parse::<ViewBox>(),
+            Err(SvgError::InvalidViewBox(_))
+        ));
+        let meet = PreserveAspectRatio::default();
+        assert_eq!(meet.to_string(), "xMidYMid");
+        assert_eq!(
+            flag.to_viewport((90.0, 60.0), meet).to_string(),
+            "matrix(.1,0,0,.1,0,0)"
+        );
+        assert!(close(
+            flag.user_to_viewport((450.0, 300.0), (100.0, 100.0), meet),
+            (50.0, 50.0)
+        ));
+        assert!(close(
+            flag.user_to_viewport((0.0, 0.0), (100.0, 100.0), meet),
+            (0.0, 50.0 - 100.0 / 3.0)
+        ));
+        let slice = PreserveAspectRatio::Slice(Align::XMinYMax);
+        assert_eq!(
+            "xMinYMax slice".parse::<PreserveAspectRatio>().ok(),
+            Some(slice)
+        );
+        assert!(close(
+            flag.user_to_viewport((0.0, 600.0), (100.0, 100.0), slice),
+            (0.0, 100.0)
+        ));
+        assert!(close(
+            flag.user_to_viewport((900.0, 600.0), (100.0, 100.0), PreserveAspectRatio::None),
+            (100.0, 100.0)
+        ));
+        assert!(flag
+            .viewport_to_user((50.0, 50.0), (100.0, 100.0), meet)
+            .is_some_and(|p| close(p, (450.0, 300.0))));
+        assert!("xMidYMid crop".parse::<PreserveAspectRatio>().is_err());
+        let mut thumbnail = Svg::new(Length::px(90.0), Length::px(90.0), flag, "thumbnail");
+        thumbnail
+            .preserve_aspect_ratio(PreserveAspectRatio::Slice(Align::XMidYMid))
+            .start()
+            .rect()
+            .width(Length::px(900.0))
+            .height(Length::px(600.0))
+            .fill("#bc002d")
+            .close()
+            .end();
+        assert!(thumbnail.to_string().contains(
+            "viewBox=\"0 0 900 600\" preserveAspectRatio=\"xMidYMid slice\" role=\"img\""
+        ));
+        assert!(thumbnail
+            .viewport_matrix()
+            .is_some_and(|m| close(m.apply((450.0, 300.0)), (45.0, 45.0))));
+        assert_eq!(thumbnail.view_box(), flag);
+        assert!(matches!(
+            Svg::new(Length::px(1.0), Length::px(1.0), "0 0 1", "broken")
+                .start()
+                .end()
+                .save("graphics", "broken"),
+            Err(SvgError::InvalidViewBox(_))
+        ));
+        assert!(thumbnail.save("graphics", "thumbnail").is_ok());
+    }
+
+    #[test]
+    pub fn gradients() {
+        let mut svg = Svg::new(
+            Length::px(164.0),
+            Length::px(28.0),
+            "0 0 164 28",
+            "gradients",
+        );
+        svg.start().title("Gradients");
+        let plastic = svg.define(LinearGradient::plastic("plastic"));
+        let sky = svg.define(
+            LinearGradient::new("sky")
+                .units(Units::UserSpaceOnUse)
+                .start(0.0, 0.0)
+                .end(0.0, 28.0)
+                .spread(SpreadMethod::Reflect)
+                .stop((0.0, Color::hex(0x0087_ceeb)))
+                .stop(Stop::new(1.0, Color::WHITE.alpha(0.5)).opacity(0.5)),
+        );
+        let sun = svg.define(
+            RadialGradient::new("sun")
+                .href(&sky)
+                .circle(
+                    Length::percent(50.0),
+                    Length::percent(50.0),
+                    Length::percent(50.0),
+                )
+                .focus(Length::percent(30.0), Length::percent(30.0), 0.0)
+                .transform(&Transform::new().rotate_around(45.0, (0.5, 0.5))),
+        );
+        svg.rect()
+            .width(Length::px(164.0))
+            .height(Length::px(28.0))
+            .fill(Paint::Url(sky))
+            .close()
+            .circle()
+            .r(Length::px(10.0))
+            .fill(Paint::Url(sun))
+            .close()
+            .rect()
+            .width(Length::px(164.0))
+            .height(Length::px(28.0))
+            .fill(Paint::Url(plastic))
+            .close()
+            .end();
+        assert_eq!(svg.definitions().len(), 3);
+        let xml = svg.to_string();
+        assert!(xml.contains("role=\"img\" id=\"gradients\"><defs><linearGradient id=\"plastic\" x2=\"0\" y2=\"100%\"><stop offset=\"0\" stop-color=\"#ffffff\" stop-opacity=\".7\"/>"));
+        assert!(xml.contains("<linearGradient id=\"sky\" gradientUnits=\"userSpaceOnUse\" x1=\"0\" y1=\"0\" x2=\"0\" y2=\"28\" spreadMethod=\"reflect\"><stop offset=\"0\" stop-color=\"#87ceeb\"/><stop offset=\"1\" stop-color=\"#ffffff\" stop-opacity=\".25\"/></linearGradient>"));
+        assert!(xml.contains("<radialGradient id=\"sun\" cx=\"50%\" cy=\"50%\" r=\"50%\" fx=\"30%\" fy=\"30%\" fr=\"0\" gradientTransform=\"rotate(45,.5,.5)\" xlink:href=\"#sky\"/></defs><title>Gradients</title>"));
+        assert!(xml.contains("<rect width=\"164px\" height=\"28px\" fill=\"url(#sky)\"/>"));
+        svg.define(LinearGradient::new("sky").stop((0.0, Color::BLACK)));
+        assert_eq!(svg.definitions().len(), 3);
+        svg.define(
+            LinearGradient::new("glow")
+                .stop((0.0, Paint::CurrentColor))
+                .stop((1.0, Paint::None)),
+        );
+        assert!(svg.to_string().contains("<linearGradient id=\"glow\"><stop offset=\"0\" stop-color=\"currentColor\"/><stop offset=\"1\" stop-color=\"#000000\" stop-opacity=\"0\"/></linearGradient>"));
+        assert!(svg.save("graphics", "gradients").is_ok());
+        let mut tinted = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "tinted");
+        tinted
+            .start()
+            .line(0.0, 5.0, 10.0, 5.0, Color::BLACK.alpha(0.5))
+            .close()
+            .end();
+        assert!(tinted.to_string().contains(
+            "<line x1=\"0\" y1=\"5\" x2=\"10\" y2=\"5\" stroke=\"#000000\" stroke-opacity=\".5\"/>"
+        ));
+        tinted.define(LinearGradient::new("tint").stop((0.0, Paint::Url("glow".to_string()))));
+        assert!(matches!(
+            tinted.save("graphics", "tinted"),
+            Err(SvgError::InvalidColor(_))
+        ));
+        let mut orphan = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "orphan");
+        orphan.start().defs().close_deps().end();
+        orphan.define(RadialGradient::new("stray").href("#missing"));
+        assert!(orphan
+            .to_string()
+            .contains("<defs><radialGradient id=\"stray\" xlink:href=\"#missing\"/></defs>"));
+        assert!(matches!(
+            orphan.save("graphics", "orphan"),
+            Err(SvgError::ResourceNotFound(_))
+        ));
+    }
+    #[test]
+    pub fn patterns() {
+        let mut svg = Svg::new(
+            Length::px(120.0),
+            Length::px(40.0),
+            "0 0 120 40",
+            "patterns",
+        );
+        svg.start().title("Patterns");
+        let hatch = svg.define(Pattern::hatch("hatch", Color::hex(0x0033_6699), 6.0, 2.0));
+        let dots = svg.define(Pattern::dots("dots", Color::BLACK, 4.0, 1.0));
+        let board = svg.define(Pattern::checkerboard(
+            "board",
+            Color::rgb(200, 200, 200),
+            5.0,
+        ));
+        let cells = svg.define(
+            Pattern::new("cells", 0.25, 0.5)
+                .content_units(Units::ObjectBoundingBox)
+                .view_box(ViewBox::new(0.0, 0.0, 10.0, 10.0))
+                .content(|tile| {
+                    tile.path().d("M0 0H10V10z").fill("#c00").close();
+                }),
+        );
+        svg.define(Pattern::hatch("veil", Color::BLACK.alpha(0.5), 4.0, 1.0));
+        svg.define(Pattern::stripes("stripes", Color::WHITE, 4.0, 1.0));
+        svg.define(Pattern::cross_hatch("grid", Color::BLACK, 8.0, 1.0));
+        for (x, fill) in [0.0, 30.0, 60.0, 90.0]
+            .into_iter()
+            .zip([hatch, dots, board, cells])
+        {
+            svg.rect()
+                .x(x)
+                .width(30.0)
+                .height(40.0)
+                .fill(Paint::Url(fill))
+                .close_rect();
+        }
+        svg.end();
+        assert_eq!(svg.definitions().len(), 7);
+        let xml = svg.to_string();
+        assert!(xml.contains("id=\"patterns\"><defs><pattern id=\"hatch\" width=\"6\" height=\"6\" patternUnits=\"userSpaceOnUse\" patternTransform=\"rotate(45)\"><line x1=\"3\" y1=\"0\" x2=\"3\" y2=\"6\" stroke=\"#336699\" stroke-width=\"2\"/></pattern>"));
+        assert!(xml.contains("<pattern id=\"dots\" width=\"4\" height=\"4\" patternUnits=\"userSpaceOnUse\"><circle cx=\"2\" cy=\"2\" r=\"1\" fill=\"#000000\"/></pattern>"));
+        assert!(xml.contains(
+            "<rect x=\"5\" y=\"5\" width=\"5\" height=\"5\" fill=\"#c8c8c8\"/></pattern>"
+        ));
+        assert!(xml.contains("<pattern id=\"cells\" width=\"0.25\" height=\"0.5\" patternContentUnits=\"objectBoundingBox\" viewBox=\"0 0 10 10\"><path d=\"M0 0H10V10z\" fill=\"#cc0000\"/></pattern>"));
+        assert!(xml.contains("<line x1=\"0\" y1=\"4\" x2=\"8\" y2=\"4\" stroke=\"#000000\" stroke-width=\"1\"/></pattern></defs>"));
+        assert!(xml.contains("<line x1=\"2\" y1=\"0\" x2=\"2\" y2=\"4\" stroke=\"#000000\" stroke-opacity=\".5\" stroke-width=\"1\"/></pattern>"));
+        assert!(
+            xml.contains("<rect x=\"90\" width=\"30\" height=\"40\" fill=\"url(#cells)\"/></svg>")
+        );
+        assert!(svg.save("graphics", "patterns").is_ok());
+        let mut broken = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "broken");
+        broken.start();
+        broken.define(Pattern::new("broken", 2.0, 2.0).content(|tile| {
+            tile.circle().r(1.0).fill("not a color").close_circle();
+        }));
+        broken.end();
+        assert!(matches!(
+            broken.save("graphics", "broken"),
+            Err(SvgError::InvalidColor(_))
+        ));
+    }
+    #[test]
+    pub fn markers() {
+        let mut svg = Svg::new(Length::px(200.0), Length::px(80.0), "0 0 200 80", "markers");
+        svg.start().title("Architecture");
+        let arrow = svg.define(Marker::arrow("arrow", Color::BLACK));
+        let dot = svg.define(Marker::dot("dot", Color::hex(0x0033_6699)));
+        for stock in [
+            Marker::open_arrow("open", Color::BLACK),
+            Marker::square("square", Color::BLACK),
+            Marker::diamond("diamond", Color::BLACK),
+            Marker::bar("bar", Color::BLACK.alpha(0.5)),
+        ] {
+            svg.define(stock);
+        }
+        let tick = svg.define(
+            Marker::new("tick")
+                .reference(0.0, 2.0)
+                .size(4.0, 4.0)
+                .units(MarkerUnits::UserSpaceOnUse)
+                .orient(Orient::Angle(90.0))
+                .content(|marker| {
+                    marker.line(0.0, 0.0, 0.0, 4.0, "red").close();
+                }),
+        );
+        svg.line(10.0, 20.0, 90.0, 20.0, "black")
+            .marker_start(&dot)
+            .marker_end(&arrow)
+            .close()
+            .build()
+            .path()
+            .d("M110 20H190V60")
+            .stroke("black")
+            .fill(Paint::None)
+            .marker_start("#bar")
+            .marker_mid(&tick)
+            .marker_end("open")
+            .close()
+            .done()
+            .end();
+        assert_eq!(svg.definitions().len(), 7);
+        let xml = svg.to_string();
+        assert!(xml.contains("id=\"markers\"><defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto-start-reverse\"><path d=\"M0 0L10 5L0 10z\" fill=\"#000000\"/></marker>"));
+        assert!(xml.contains("<path d=\"M1 1L9 5L1 9\" fill=\"none\" stroke=\"#000000\" stroke-width=\"1.5\"/></marker>"));
+        assert!(xml.contains("<line x1=\"5\" y1=\"0\" x2=\"5\" y2=\"10\" stroke=\"#000000\" stroke-opacity=\".5\" stroke-width=\"2\"/></marker>"));
+        assert!(xml.contains("<marker id=\"tick\" refX=\"0\" refY=\"2\" markerWidth=\"4\" markerHeight=\"4\" markerUnits=\"userSpaceOnUse\" orient=\"90\"><line x1=\"0\" y1=\"0\" x2=\"0\" y2=\"4\" stroke=\"#ff0000\"/></marker></defs>"));
+        assert!(xml.contains("<line x1=\"10\" y1=\"20\" x2=\"90\" y2=\"20\" stroke=\"#000000\" marker-start=\"url(#dot)\" marker-end=\"url(#arrow)\"/>"));
+        assert!(xml.contains("marker-start=\"url(#bar)\" marker-mid=\"url(#tick)\" marker-end=\"url(#open)\"/></svg>"));
+        assert!(svg.save("graphics", "markers").is_ok());
+    }
+    #[test]
+    pub fn clipping() {
+        let mut svg = Svg::new(Length::px(120.0), Length::px(40.0), "0 0 120 40", "profile");
+        svg.start().title("Avatar");
+        let avatar = svg.define(ClipPath::circle("avatar"));
+        let window = svg.define(
+            ClipPath::new("window")
+                .transform(&Transform::new().translate(40.0, 0.0))
+                .content(|clip| {
+                    clip.rect().width(40.0).height(20.0).close_rect();
+                }),
+        );
+        let fade = svg.define(
+            LinearGradient::new("fade")
+                .stop((0.0, Color::WHITE))
+                .stop((1.0, Color::BLACK)),
+        );
+        let faded = svg.define(
+            Mask::new("faded")
+                .area(0.0, 0.0, 1.0, 1.0)
+                .content_units(Units::ObjectBoundingBox)
+                .content(|mask| {
+                    mask.rect()
+                        .width(1.0)
+                        .height(1.0)
+                        .fill(Paint::Url(fade))
+                        .close_rect();
+                }),
+        );
+        let shape = svg.define(
+            Mask::new("shape")
+                .units(Units::UserSpaceOnUse)
+                .kind(MaskType::Alpha)
+                .content(|mask| {
+                    mask.circle().cx(100.0).cy(20.0).r(20.0).close_circle();
+                }),
+        );
+        svg.image_from_bytes(ICON)
+            .width(40.0)
+            .height(40.0)
+            .clip_path(&avatar)
+            .close()
+            .rect()
+            .width(120.0)
+            .height(40.0)
+            .fill("#4c1")
+            .clip_path("#window")
+            .mask(&faded)
+            .close_rect()
+            .build()
+            .g()
+            .mask(&shape)
+            .clip_path(&window)
+            .close()
+            .done()
+            .end();
+        let xml = svg.to_string();
+        assert!(xml.contains("id=\"profile\"><defs><clipPath id=\"avatar\" clipPathUnits=\"objectBoundingBox\"><circle cx=\"0.5\" cy=\"0.5\" r=\"0.5\"/></clipPath><clipPath id=\"window\" transform=\"translate(40,0)\"><rect width=\"40\" height=\"20\"/></clipPath>"));
+        assert!(xml.contains("<mask id=\"faded\" x=\"0\" y=\"0\" width=\"1\" height=\"1\" maskContentUnits=\"objectBoundingBox\"><rect width=\"1\" height=\"1\" fill=\"url(#fade)\"/></mask>"));
+        assert!(xml.contains("<mask id=\"shape\" maskUnits=\"userSpaceOnUse\" mask-type=\"alpha\"><circle cx=\"100\" cy=\"20\" r=\"20\"/></mask></defs>"));
+        assert!(xml.contains("width=\"40\" height=\"40\" clip-path=\"url(#avatar)\"/>"));
+        assert!(xml.contains("<rect width=\"120\" height=\"40\" fill=\"#44cc11\" clip-path=\"url(#window)\" mask=\"url(#faded)\"/><g mask=\"url(#shape)\" clip-path=\"url(#window)\"/></svg>"));
+        assert!(svg.save("graphics", "avatar").is_ok());
+        let mut broken = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "broken");
+        broken.start();
+        broken.define(Mask::new("torn").content(|mask| {
+            mask.path().d("M0 0 L").close();
+        }));
+        broken.end();
+        assert!(matches!(
+            broken.save("graphics", "broken"),
+            Err(SvgError::InvalidPath { .. })
+        ));
+        let mut duplicate = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "clip");
+        duplicate.start().rect().id("disc").close_rect();
+        duplicate.define(ClipPath::circle("disc"));
+        duplicate.define(ClipPath::circle("clip"));
+        duplicate.end();
+        assert!(matches!(
+            duplicate.save("graphics", "duplicate"),
+            Err(SvgError::DuplicateId(id)) if id == "disc"
+        ));
+    }
+    #[test]
+    pub fn filters() {
+        let mut svg = Svg::new(
+            Length::px(100.0),
+            Length::px(100.0),
+            "0 0 100 100",
+            "filters",
+        );
+        svg.start().title("Filters");
+        let shadow = svg.define(
+            Filter::new("shadow")
+                .area(-0.2, -0.2, 1.4, 1.4)
+                .gaussian_blur(Input::SourceAlpha, 2.0)
+                .offset(Input::Previous, 1.0, 1.0)
+                .result("shade")
+                .flood(Color::BLACK.alpha(0.5))
+                .composite(Input::Previous, "shade", CompositeOperator::In)
+                .merge(vec![Input::Previous, Input::SourceGraphic]),
+        );
+        let paper = svg.define(
+            Filter::new("paper")
+                .units(Units::UserSpaceOnUse)
+                .primitive_units(Units::UserSpaceOnUse)
+                .color_interpolation(ColorInterpolation::Srgb)
+                .turbulence(TurbulenceType::FractalNoise, (0.04, 0.05), 3, 7.0, true)
+                .result("noise")
+                .displacement_map("SourceGraphic", "noise", 4.0, Channel::R, Channel::G)
+                .diffuse_lighting(
+                    "noise",
+                    Color::WHITE,
+                    2.0,
+                    1.0,
+                    Light::Distant {
+                        azimuth: 45.0,
+                        elevation: 60.0,
+                    },
+                )
+                .specular_lighting(
+                    "noise",
+                    Color::WHITE,
+                    1.5,
+                    0.5,
+                    20.0,
+                    Light::Spot {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 50.0,
+                        at: (50.0, 50.0, 0.0),
+                        exponent: 1.0,
+                        cone: Some(30.0),
+                    },
+                )
+                .blend(Input::Previous, "SourceGraphic", BlendMode::Multiply)
+                .composite(
+                    Input::Previous,
+                    Input::SourceAlpha,
+                    CompositeOperator::Arithmetic {
+                        k1: 0.0,
+                        k2: 1.0,
+                        k3: 0.5,
+                        k4: 0.0,
+                    },
+                ),
+        );
+        svg.define(
+            Filter::new("retouch")
+                .color_matrix(Input::SourceGraphic, ColorMatrix::Saturate(0.5))
+                .color_matrix(Input::Previous, ColorMatrix::LuminanceToAlpha)
+                .component_transfer(
+                    Input::Previous,
+                    &TransferFunction::Linear {
+                        slope: 1.2,
+                        intercept: -0.1,
+                    },
+                    &TransferFunction::Identity,
+                    &TransferFunction::Table(vec![0.0, 0.5, 1.0]),
+                    &TransferFunction::Gamma {
+                        amplitude: 1.0,
+                        exponent: 2.2,
+                        offset: 0.0,
+                    },
+                )
+                .convolve_matrix(
+                    Input::Previous,
+                    &[0.0, -1.0, 0.0, -1.0, 5.0, -1.0, 0.0, -1.0, 0.0],
+                    EdgeMode::None,
+                    true,
+                )
+                .morphology(Input::Previous, MorphologyOperator::Dilate, 1.0)
+                .image("#logo")
+                .tile(Input::Previous)
+                .drop_shadow(Input::SourceGraphic, 2.0, 2.0, 1.0, Color::hex(0x0033_6699)),
+        );
+        svg.rect()
+            .width(50.0)
+            .height(50.0)
+            .filter(&shadow)
+            .close_rect()
+            .build()
+            .circle()
+            .r(20.0)
+            .filter(&paper)
+            .close()
+            .rect()
+            .filter("none")
+            .close()
+            .circle()
+            .filter("inherit")
+            .close()
+            .rect()
+            .filter("saturate(50%)")
+            .close()
+            .done()
+            .rect()
+            .filter("blur(2px)")
+            .close_rect()
+            .circle()
+            .filter("none")
+            .close_circle()
+            .rect()
+            .filter("inherit")
+            .close_rect()
+            .end();
+        let xml = svg.to_string();
+        assert!(xml.contains("<defs><filter id=\"shadow\" x=\"-0.2\" y=\"-0.2\" width=\"1.4\" height=\"1.4\"><feGaussianBlur in=\"SourceAlpha\" stdDeviation=\"2\"/><feOffset dx=\"1\" dy=\"1\" result=\"shade\"/><feFlood flood-color=\"#000000\" flood-opacity=\".5\"/><feComposite in2=\"shade\" operator=\"in\"/><feMerge><feMergeNode/><feMergeNode in=\"SourceGraphic\"/></feMerge></filter>"));
+        assert!(xml.contains("<feTurbulence type=\"fractalNoise\" baseFrequency=\".04 .05\" numOctaves=\"3\" seed=\"7\" stitchTiles=\"stitch\" result=\"noise\"/><feDisplacementMap in=\"SourceGraphic\" in2=\"noise\" scale=\"4\" xChannelSelector=\"R\" yChannelSelector=\"G\"/>"));
+        assert!(xml.contains("<feDiffuseLighting in=\"noise\" lighting-color=\"#ffffff\" surfaceScale=\"2\" diffuseConstant=\"1\"><feDistantLight azimuth=\"45\" elevation=\"60\"/></feDiffuseLighting>"));
+        assert!(xml.contains("<feSpotLight x=\"0\" y=\"0\" z=\"50\" pointsAtX=\"50\" pointsAtY=\"50\" pointsAtZ=\"0\" specularExponent=\"1\" limitingConeAngle=\"30\"/>"));
+        assert!(xml.contains("<feComposite in2=\"SourceAlpha\" operator=\"arithmetic\" k1=\"0\" k2=\"1\" k3=\".5\" k4=\"0\"/></filter>"));
+        assert!(xml.contains("<feComponentTransfer><feFuncR type=\"linear\" slope=\"1.2\" intercept=\"-.1\"/><feFuncB type=\"table\" tableValues=\"0 .5 1\"/><feFuncA type=\"gamma\" amplitude=\"1\" exponent=\"2.2\" offset=\"0\"/></feComponentTransfer>"));
+        assert!(xml.contains("<feConvolveMatrix order=\"3\" kernelMatrix=\"0 -1 0 -1 5 -1 0 -1 0\" edgeMode=\"none\" preserveAlpha=\"true\"/><feMorphology operator=\"dilate\" radius=\"1\"/><feImage xlink:href=\"#logo\"/><feTile/>"));
+        assert!(xml.contains("<feDropShadow in=\"SourceGraphic\" dx=\"2\" dy=\"2\" stdDeviation=\"1\" flood-color=\"#336699\"/></filter></defs>"));
+        assert!(xml.contains("<rect width=\"50\" height=\"50\" filter=\"url(#shadow)\"/><circle r=\"20\" filter=\"url(#paper)\"/><rect filter=\"none\"/><circle filter=\"inherit\"/><rect filter=\"saturate(50%)\"/><rect filter=\"blur(2px)\"/><circle filter=\"none\"/><rect filter=\"inherit\"/></svg>"));
+        assert!(svg.save("graphics", "filters").is_ok());
+        let mut primitives = Svg::new(
+            Length::px(10.0),
+            Length::px(10.0),
+            "0 0 10 10",
+            "primitives",
+        );
+        primitives
+            .start()
+            .defs()
+            .filter_element()
+            .id("moved")
+            .fe_offset()
+            .dx("2")
+            .result("offset")
+            .close()
+            .fe_blend()
+            .in1("SourceGraphic")
+            .in2("offset")
+            .close()
+            .close_filter()
+            .close_deps()
+            .rect()
+            .width(10.0)
+            .height(10.0)
+            .filter("moved")
+            .close_rect()
+            .end();
+        assert!(primitives.to_string().contains("<defs><filter id=\"moved\"><feOffset dx=\"2\" result=\"offset\"/><feBlend in=\"SourceGraphic\" in2=\"offset\"/></filter></defs><rect width=\"10\" height=\"10\" filter=\"url(#moved)\"/>"));
+        assert!(primitives.save("graphics", "primitives").is_ok());
+        for filter in [
+            Filter::new("unknown").offset("missing", 1.0, 1.0),
+            Filter::new("unnamed").result("first"),
+            Filter::new("kernel").convolve_matrix(
+                Input::Previous,
+                &[1.0, 2.0],
+                EdgeMode::Wrap,
+                false,
+            ),
+        ] {
+            let mut broken = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "broken");
+            broken.start();
+            broken.define(filter);
+            broken.end();
+            assert!(matches!(
+                broken.save("graphics", "broken"),
+                Err(SvgError::InvalidFilter(_))
+            ));
+        }
+    }
+    #[test]
+    pub fn effects() {
+        let mut svg = Svg::new(Length::px(200.0), Length::px(60.0), "0 0 200 60", "effects");
+        svg.start()
+            .title("Effects")
+            .rect()
+            .width(90.0)
+            .height(20.0)
+            .fill("#4c1")
+            .drop_shadow(1.0, 2.0, 1.5, Color::BLACK.alpha(0.4))
+            .close_rect()
+            .rect()
+            .x(100.0)
+            .width(90.0)
+            .height(20.0)
+            .fill("#e05d44")
+            .drop_shadow(1.0, 2.0, 1.5, Color::BLACK.alpha(0.4))
+            .close_rect()
+            .circle()
+            .cx(10.0)
+            .cy(40.0)
+            .r(8.0)
+            .drop_shadow(0.0, 0.0, 3.0, Color::BLACK)
+            .close_circle()
+            .circle()
+            .cx(30.0)
+            .cy(40.0)
+            .r(8.0)
+            .outer_glow(2.0, Color::hex(0x00ff_d700))
+            .close_circle()
+            .circle()
+            .cx(50.0)
+            .cy(40.0)
+            .r(8.0)
+            .inner_shadow(1.0, 1.0, 2.0, Color::BLACK)
+            .close_circle()
+            .image_from_bytes(ICON)
+            .x(70.0)
+            .y(30.0)
+            .width(20.0)
+            .height(20.0)
+            .grayscale()
+            .close()
+            .g()
+            .sepia()
+            .close_group()
+            .g()
+            .blur(0.5)
+            .close_group()
+            .g()
+            .duotone(Color::hex(0x0000_0066), Color::hex(0x00ff_cc00))
+            .close_group()
+            .g()
+            .emboss()
+            .close_group()
+            .end();
+        let ids: Vec<&str> = svg
+            .definitions()
+            .iter()
+            .filter_map(|d| d.attribute("id"))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "drop-shadow",
+                "drop-shadow-2",
+                "outer-glow",
+                "inner-shadow",
+                "grayscale",
+                "sepia",
+                "blur",
+                "duotone",
+                "emboss"
+            ]
+        );
+        let xml = svg.to_string();
+        assert!(xml.contains("<defs><filter id=\"drop-shadow\" x=\"-50%\" y=\"-50%\" width=\"200%\" height=\"200%\"><feGaussianBlur in=\"SourceAlpha\" stdDeviation=\"1.5\"/><feOffset dx=\"1\" dy=\"2\" result=\"shadow\"/><feFlood flood-color=\"#000000\" flood-opacity=\".4\"/><feComposite in2=\"shadow\" operator=\"in\"/><feMerge><feMergeNode/><feMergeNode in=\"SourceGraphic\"/></feMerge></filter>"));
+        assert!(xml.contains("<filter id=\"inner-shadow\"><feFlood flood-color=\"#000000\"/><feComposite in2=\"SourceAlpha\" operator=\"out\"/><feOffset dx=\"1\" dy=\"1\"/><feGaussianBlur stdDeviation=\"2\"/><feComposite in2=\"SourceAlpha\" operator=\"in\"/><feMerge><feMergeNode in=\"SourceGraphic\"/><feMergeNode/></feMerge></filter>"));
+        for id in ["grayscale", "sepia", "emboss"] {
+            assert!(xml.contains(
+                format!("<filter id=\"{id}\" color-interpolation-filters=\"sRGB\">").as_str()
+            ));
+        }
+        assert!(xml.contains("<feFuncR type=\"table\" tableValues=\"0 1\"/><feFuncG type=\"table\" tableValues=\"0 .8\"/><feFuncB type=\"table\" tableValues=\".4 0\"/>"));
+        assert!(xml.contains("<rect width=\"90\" height=\"20\" fill=\"#44cc11\" filter=\"url(#drop-shadow)\"/><rect x=\"100\" width=\"90\" height=\"20\" fill=\"#e05d44\" filter=\"url(#drop-shadow)\"/><circle cx=\"10\" cy=\"40\" r=\"8\" filter=\"url(#drop-shadow-2)\"/>"));
+        assert!(xml.contains("<g filter=\"url(#sepia)\"/><g filter=\"url(#blur)\"/><g filter=\"url(#duotone)\"/><g filter=\"url(#emboss)\"/></svg>"));
+        assert!(svg.save("graphics", "effects").is_ok());
+        let mut taken = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "blur");
+        taken
+            .start()
+            .rect()
+            .width(10.0)
+            .height(10.0)
+            .blur(1.0)
+            .close_rect()
+            .end();
+        assert_eq!(taken.definitions()[0].attribute("id"), Some("blur-2"));
+        assert!(taken.save("graphics", "taken").is_ok());
+        assert_eq!(
+            effect::blur("soft", 2.0).to_node().to_string(),
+            "<filter id=\"soft\"><feGaussianBlur in=\"SourceGraphic\" stdDeviation=\"2\"/></filter>"
+        );
+    }
+    #[test]
+    pub fn animations() {
+        let mut svg = Svg::new(
+            Length::px(200.0),
+            Length::px(100.0),
+            "0 0 200 100",
+            "animations",
+        );
+        svg.start()
+            .title("Animations")
+            .path()
+            .id("track")
+            .d("M10 50C60 0 140 100 190 50")
+            .fill(Paint::None)
+            .stroke("gray")
+            .close()
+            .rect()
+            .id("button")
+            .width(20.0)
+            .height(20.0)
+            .animation(
+                Animate::new("rx")
+                    .id("round")
+                    .values(&[0, 10, 0])
+                    .dur(Duration::from_millis(1500))
+                    .begin(Begin::event("click"))
+                    .begin(Duration::from_secs(2))
+                    .easing(Easing::EaseInOut)
+                    .repeat(Repeat::Count(2.5))
+                    .freeze(),
+            )
+            .animation(
+                AnimateTransform::new(TransformType::Rotate)
+                    .between("0 10 10", "360 10 10")
+                    .dur(Duration::from_secs(3))
+                    .begin(Begin::after("round").delay(Duration::from_secs(1)))
+                    .easing(Easing::CubicBezier(0.1, 0.7, 1.0, 0.1))
+                    .additive()
+                    .repeat(Repeat::Indefinite),
+            )
+            .animation(
+                Set::new("visibility", "hidden")
+                    .begin(Begin::event_on("#reset", "click").delay(Duration::from_millis(250)))
+                    .begin(Begin::Indefinite),
+            )
+            .close_rect()
+            .build()
+            .circle()
+            .r(4.0)
+            .animation(
+                AnimateMotion::new()
+                    .mpath("#track")
+                    .rotate(MotionRotate::Auto)
+                    .key_points(&[0.0, 1.0])
+                    .key_times(&[0.0, 1.0])
+                    .calc_mode(CalcMode::Linear)
+                    .dur(Duration::from_secs(4))
+                    .begin(Begin::with("round"))
+                    .repeat(Repeat::For(Duration::from_secs(20))),
+            )
+            .close()
+            .done()
+            .end();
+        let xml = svg.to_string();
+        assert!(xml.contains("<animate attributeName=\"rx\" id=\"round\" dur=\"1500ms\" begin=\"click;2s\" repeatCount=\"2.5\" fill=\"freeze\" values=\"0;10;0\" keyTimes=\"0;.5;1\" calcMode=\"spline\" keySplines=\".42 0 .58 1;.42 0 .58 1\"/>"));
+        assert!(xml.contains("<animateTransform attributeName=\"transform\" type=\"rotate\" from=\"0 10 10\" to=\"360 10 10\" dur=\"3s\" begin=\"round.end+1s\" additive=\"sum\" repeatCount=\"indefinite\" keyTimes=\"0;1\" calcMode=\"spline\" keySplines=\".1 .7 1 .1\"/>"));
+        assert!(xml.contains("<set attributeName=\"visibility\" to=\"hidden\" begin=\"reset.click+250ms;indefinite\"/></rect>"));
+        assert!(xml.contains("<circle r=\"4\"><animateMotion rotate=\"auto\" keyPoints=\"0;1\" calcMode=\"linear\" dur=\"4s\" begin=\"round.begin\" repeatDur=\"20s\" keyTimes=\"0;1\"><mpath xlink:href=\"#track\"/></animateMotion></circle>"));
+        assert!(svg.save("animates", "animations").is_ok());
+        let still = Animate::new("opacity")
+            .values(&[1])
+            .easing(Easing::EaseInOut)
+            .to_node();
+        assert_eq!(
+            still.to_string(),
+            "<animate attributeName=\"opacity\" values=\"1\"/>"
         );
+        let mut broken = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "broken");
+        broken
+            .start()
+            .circle()
+            .animation(AnimateMotion::new().path("M0 0 L"))
+            .close_circle()
+            .end();
+        assert!(matches!(
+            broken.save("animates", "broken"),
+            Err(SvgError::InvalidPath { .. })
+        ));
+        let mut broken = Svg::new(Length::px(10.0), Length::px(10.0), "0 0 10 10", "broken");
+        broken
+            .start()
+            .circle()
+            .animation(
+                AnimateMotion::new()
+                    .values(&["0,0", "10,10"])
+                    .key_times(&[0.0, 0.5, 1.0]),
+            )
+            .close_circle()
+            .end();
+        assert!(matches!(
+            broken.save("animates", "broken"),
+            Err(SvgError::InvalidAnimation(_))
+        ));
     }
 }